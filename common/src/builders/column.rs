@@ -1,8 +1,26 @@
+use std::marker::PhantomData;
+
 #[allow(unused)]
-#[derive(Default, Debug, Clone)]
-pub struct ColumnBaseBuilder<DataType: Default, ColumnType: Default, DataMode: Default> {
+#[derive(Debug, Clone)]
+pub struct ColumnBaseBuilder<DataType, ColumnType: Default, DataMode: Default> {
     pub name: &'static str,
+    pub table: &'static str,
     pub mode: DataMode,
-    pub data_type: DataType,
+    pub data_type: PhantomData<fn() -> DataType>,
     pub column_type: ColumnType,
 }
+
+// Written by hand so the Rust type of a column, e.g. `SystemTime`, needn't implement `Default`
+impl<DataType, ColumnType: Default, DataMode: Default> Default
+    for ColumnBaseBuilder<DataType, ColumnType, DataMode>
+{
+    fn default() -> Self {
+        Self {
+            name: "",
+            table: "",
+            mode: DataMode::default(),
+            data_type: PhantomData,
+            column_type: ColumnType::default(),
+        }
+    }
+}
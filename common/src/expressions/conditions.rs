@@ -1,47 +1,371 @@
-pub fn eq() {}
+use crate::{traits::Comparable, SQLChunk, SQL};
 
-pub fn ne() {}
+use super::Expression;
 
-pub fn and() {}
+fn binary<V, L, R>(left: L, operator: &'static str, right: R) -> SQL<V, bool>
+where
+    L: Expression<V>,
+    R: Expression<V>,
+{
+    SQL::default()
+        .append(left.into_sql())
+        .push_str(operator)
+        .append(right.into_sql())
+}
 
-pub fn or() {}
+fn postfix<V, E: Expression<V>>(expression: E, operator: &'static str) -> SQL<V, bool> {
+    expression.into_sql().cast().push_str(operator)
+}
 
-pub fn not() {}
+pub fn eq<V, L, R>(left: L, right: R) -> SQL<V, bool>
+where
+    L: Expression<V>,
+    R: Expression<V>,
+    L::DataType: Comparable<R::DataType>,
+{
+    binary(left, " = ", right)
+}
 
-pub fn gt() {}
+pub fn ne<V, L, R>(left: L, right: R) -> SQL<V, bool>
+where
+    L: Expression<V>,
+    R: Expression<V>,
+    L::DataType: Comparable<R::DataType>,
+{
+    binary(left, " <> ", right)
+}
 
-pub fn gte() {}
+/// A clause of [`and`] and [`or`]: a condition, or `None` to leave it out of the group.
+pub trait Clause<V> {
+    fn into_clause(self) -> Option<SQL<V, bool>>;
+}
 
-pub fn lt() {}
+impl<V> Clause<V> for SQL<V, bool> {
+    fn into_clause(self) -> Option<SQL<V, bool>> {
+        Some(self)
+    }
+}
 
-pub fn lte() {}
+impl<V> Clause<V> for Option<SQL<V, bool>> {
+    fn into_clause(self) -> Option<SQL<V, bool>> {
+        self
+    }
+}
 
-pub fn in_array() {}
+fn group<V, C, I>(separator: &'static str, empty: &'static str, clauses: I) -> SQL<V, bool>
+where
+    C: Clause<V>,
+    I: IntoIterator<Item = C>,
+{
+    let mut flattened = vec![];
+    for clause in clauses.into_iter().filter_map(Clause::into_clause) {
+        // A nested group joined by the same operator is spliced in, since its parentheses
+        // would not change the meaning.
+        match <[SQLChunk<V>; 1]>::try_from(clause.into_chunks()) {
+            Ok(
+                [SQLChunk::Group {
+                    separator: nested,
+                    clauses,
+                }],
+            ) if nested == separator => flattened.extend(clauses),
+            Ok(chunks) => flattened.push(SQL::from_chunks(chunks.into())),
+            Err(chunks) => flattened.push(SQL::from_chunks(chunks)),
+        }
+    }
 
-pub fn not_in_array() {}
+    match flattened.len() {
+        0 => SQL::raw(empty).cast(),
+        1 => flattened.remove(0).cast(),
+        _ => SQL::default().push(SQLChunk::Group {
+            separator,
+            // Clauses of several chunks, such as `a OR b` from `sql!`, are parenthesized so
+            // the separator cannot bind tighter than the operators inside them.
+            clauses: flattened
+                .into_iter()
+                .map(|clause| match clause.chunks() {
+                    [_] => clause,
+                    _ => SQL::default().nest(clause),
+                })
+                .collect(),
+        }),
+    }
+}
 
-pub fn is_null() {}
+/// `(a AND b AND ...)`, leaving out `None` clauses and splicing in nested `and` groups. A single
+/// clause renders without parentheses and no clause at all renders as `1 = 1`, so an empty
+/// dynamic filter matches everything.
+pub fn and<V, C, I>(clauses: I) -> SQL<V, bool>
+where
+    C: Clause<V>,
+    I: IntoIterator<Item = C>,
+{
+    group(" AND ", "1 = 1", clauses)
+}
 
-pub fn is_not_null() {}
+/// `(a OR b OR ...)`, leaving out `None` clauses and splicing in nested `or` groups. A single
+/// clause renders without parentheses and no clause at all renders as `1 = 0`.
+pub fn or<V, C, I>(clauses: I) -> SQL<V, bool>
+where
+    C: Clause<V>,
+    I: IntoIterator<Item = C>,
+{
+    group(" OR ", "1 = 0", clauses)
+}
 
-pub fn exists() {}
+/// `NOT (condition)`. Use `Option::map` to negate an optional clause.
+pub fn not<V>(condition: SQL<V, bool>) -> SQL<V, bool> {
+    match condition.chunks() {
+        // Groups already carry their parentheses.
+        [SQLChunk::Group { .. }] => SQL::raw("NOT ").append(condition).cast(),
+        _ => SQL::raw("NOT ").nest(condition).cast(),
+    }
+}
 
-pub fn not_exists() {}
+pub fn gt<V, L, R>(left: L, right: R) -> SQL<V, bool>
+where
+    L: Expression<V>,
+    R: Expression<V>,
+    L::DataType: Comparable<R::DataType>,
+{
+    binary(left, " > ", right)
+}
 
-pub fn between() {}
+pub fn gte<V, L, R>(left: L, right: R) -> SQL<V, bool>
+where
+    L: Expression<V>,
+    R: Expression<V>,
+    L::DataType: Comparable<R::DataType>,
+{
+    binary(left, " >= ", right)
+}
 
-pub fn not_between() {}
+pub fn lt<V, L, R>(left: L, right: R) -> SQL<V, bool>
+where
+    L: Expression<V>,
+    R: Expression<V>,
+    L::DataType: Comparable<R::DataType>,
+{
+    binary(left, " < ", right)
+}
 
-pub fn like() {}
+pub fn lte<V, L, R>(left: L, right: R) -> SQL<V, bool>
+where
+    L: Expression<V>,
+    R: Expression<V>,
+    L::DataType: Comparable<R::DataType>,
+{
+    binary(left, " <= ", right)
+}
 
-pub fn not_like() {}
+fn in_list<V, E, R, I>(expression: E, operator: &'static str, values: I) -> Option<SQL<V, bool>>
+where
+    E: Expression<V>,
+    R: Expression<V>,
+    I: IntoIterator<Item = R>,
+{
+    let values: Vec<SQL<V, R::DataType>> = values.into_iter().map(R::into_sql).collect();
+    if values.is_empty() {
+        return None;
+    }
 
-pub fn ilike() {}
+    Some(
+        expression
+            .into_sql()
+            .cast()
+            .push_str(operator)
+            .push_str("(")
+            .append(SQL::<V>::join(values, ", "))
+            .push_str(")"),
+    )
+}
 
-pub fn not_ilike() {}
+/// `expression IN (values)`. An empty list matches nothing and renders as `1 = 0`, since not
+/// every dialect accepts `IN ()`.
+pub fn in_array<V, E, R, I>(expression: E, values: I) -> SQL<V, bool>
+where
+    E: Expression<V>,
+    R: Expression<V>,
+    I: IntoIterator<Item = R>,
+    E::DataType: Comparable<R::DataType>,
+{
+    in_list(expression, " IN ", values).unwrap_or_else(|| SQL::raw("1 = 0").cast())
+}
 
-pub fn array_contains() {}
+/// `expression NOT IN (values)`. An empty list matches everything and renders as `1 = 1`.
+pub fn not_in_array<V, E, R, I>(expression: E, values: I) -> SQL<V, bool>
+where
+    E: Expression<V>,
+    R: Expression<V>,
+    I: IntoIterator<Item = R>,
+    E::DataType: Comparable<R::DataType>,
+{
+    in_list(expression, " NOT IN ", values).unwrap_or_else(|| SQL::raw("1 = 1").cast())
+}
 
-pub fn array_contained() {}
+pub fn is_null<V, E: Expression<V>>(expression: E) -> SQL<V, bool> {
+    postfix(expression, " IS NULL")
+}
 
-pub fn array_overlaps() {}
+pub fn is_not_null<V, E: Expression<V>>(expression: E) -> SQL<V, bool> {
+    postfix(expression, " IS NOT NULL")
+}
+
+/// `EXISTS (subquery)`.
+pub fn exists<V, T>(subquery: SQL<V, T>) -> SQL<V, bool> {
+    SQL::raw("EXISTS ").nest(subquery).cast()
+}
+
+/// `NOT EXISTS (subquery)`.
+pub fn not_exists<V, T>(subquery: SQL<V, T>) -> SQL<V, bool> {
+    SQL::raw("NOT EXISTS ").nest(subquery).cast()
+}
+
+fn between_range<V, E, L, H>(expression: E, operator: &'static str, low: L, high: H) -> SQL<V, bool>
+where
+    E: Expression<V>,
+    L: Expression<V>,
+    H: Expression<V>,
+{
+    expression
+        .into_sql()
+        .cast()
+        .push_str(operator)
+        .append(low.into_sql())
+        .push_str(" AND ")
+        .append(high.into_sql())
+}
+
+pub fn between<V, E, L, H>(expression: E, low: L, high: H) -> SQL<V, bool>
+where
+    E: Expression<V>,
+    L: Expression<V>,
+    H: Expression<V>,
+    E::DataType: Comparable<L::DataType> + Comparable<H::DataType>,
+{
+    between_range(expression, " BETWEEN ", low, high)
+}
+
+pub fn not_between<V, E, L, H>(expression: E, low: L, high: H) -> SQL<V, bool>
+where
+    E: Expression<V>,
+    L: Expression<V>,
+    H: Expression<V>,
+    E::DataType: Comparable<L::DataType> + Comparable<H::DataType>,
+{
+    between_range(expression, " NOT BETWEEN ", low, high)
+}
+
+/// `expression LIKE pattern`, for text operands.
+pub fn like<'a, V, E>(expression: E, pattern: &'a str) -> SQL<V, bool>
+where
+    V: From<String>,
+    E: Expression<V>,
+    E::DataType: Comparable<&'a str>,
+{
+    binary(expression, " LIKE ", pattern)
+}
+
+/// `expression NOT LIKE pattern`, for text operands.
+pub fn not_like<'a, V, E>(expression: E, pattern: &'a str) -> SQL<V, bool>
+where
+    V: From<String>,
+    E: Expression<V>,
+    E::DataType: Comparable<&'a str>,
+{
+    binary(expression, " NOT LIKE ", pattern)
+}
+
+/// Case-insensitive `LIKE`, implemented by the parameter type of each dialect that supports it
+/// with the SQL that dialect needs.
+#[diagnostic::on_unimplemented(
+    message = "the dialect binding `{Self}` has no case-insensitive `LIKE`",
+    label = "`ilike` is not supported here"
+)]
+pub trait CaseInsensitiveLike: Sized {
+    fn ilike(expression: SQL<Self>, pattern: SQL<Self>) -> SQL<Self, bool>;
+
+    fn not_ilike(expression: SQL<Self>, pattern: SQL<Self>) -> SQL<Self, bool>;
+}
+
+/// `expression ILIKE pattern`, for text operands in dialects that support it.
+pub fn ilike<'a, V, E>(expression: E, pattern: &'a str) -> SQL<V, bool>
+where
+    V: From<String> + CaseInsensitiveLike,
+    E: Expression<V>,
+    E::DataType: Comparable<&'a str>,
+{
+    V::ilike(expression.into_sql().cast(), pattern.into_sql().cast())
+}
+
+/// `expression NOT ILIKE pattern`, for text operands in dialects that support it.
+pub fn not_ilike<'a, V, E>(expression: E, pattern: &'a str) -> SQL<V, bool>
+where
+    V: From<String> + CaseInsensitiveLike,
+    E: Expression<V>,
+    E::DataType: Comparable<&'a str>,
+{
+    V::not_ilike(expression.into_sql().cast(), pattern.into_sql().cast())
+}
+
+/// An expression the dialect binding `V` can query as an array of `Element`s, with the SQL of
+/// [`array_contains`], [`array_contained`] and [`array_overlaps`] in that dialect.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not an array in this dialect",
+    label = "not an array"
+)]
+pub trait ArrayExpression<V>: Expression<V> {
+    type Element;
+
+    /// Every one of `values` is in the array.
+    fn contains(self, values: Vec<SQL<V>>) -> SQL<V, bool>;
+
+    /// Every element of the array is one of `values`.
+    fn contained(self, values: Vec<SQL<V>>) -> SQL<V, bool>;
+
+    /// The array and `values` have an element in common.
+    fn overlaps(self, values: Vec<SQL<V>>) -> SQL<V, bool>;
+}
+
+fn array_values<V, R, I>(values: I) -> Vec<SQL<V>>
+where
+    R: Expression<V>,
+    I: IntoIterator<Item = R>,
+{
+    values
+        .into_iter()
+        .map(|value| value.into_sql().cast())
+        .collect()
+}
+
+/// `array @> values`: the array holds every one of `values`.
+pub fn array_contains<V, E, R, I>(array: E, values: I) -> SQL<V, bool>
+where
+    E: ArrayExpression<V>,
+    R: Expression<V>,
+    I: IntoIterator<Item = R>,
+    E::Element: Comparable<R::DataType>,
+{
+    array.contains(array_values(values))
+}
+
+/// `array <@ values`: every element of the array is one of `values`.
+pub fn array_contained<V, E, R, I>(array: E, values: I) -> SQL<V, bool>
+where
+    E: ArrayExpression<V>,
+    R: Expression<V>,
+    I: IntoIterator<Item = R>,
+    E::Element: Comparable<R::DataType>,
+{
+    array.contained(array_values(values))
+}
+
+/// `array && values`: the array holds at least one of `values`.
+pub fn array_overlaps<V, E, R, I>(array: E, values: I) -> SQL<V, bool>
+where
+    E: ArrayExpression<V>,
+    R: Expression<V>,
+    I: IntoIterator<Item = R>,
+    E::Element: Comparable<R::DataType>,
+{
+    array.overlaps(array_values(values))
+}
@@ -1 +1,75 @@
-mod conditions;
+use crate::{SQLFragment, SQL};
+
+pub mod conditions;
+
+/// Operand of an expression: a column, a [`SQL`] fragment, or a Rust value bound as a parameter.
+pub trait Expression<V> {
+    type DataType;
+
+    fn into_sql(self) -> SQL<V, Self::DataType>;
+}
+
+impl<V, T> Expression<V> for SQL<V, T> {
+    type DataType = T;
+
+    fn into_sql(self) -> SQL<V, T> {
+        // Nested fragments are grouped so operator precedence never changes their meaning.
+        SQL::default().nest(self)
+    }
+}
+
+macro_rules! impl_param_expression {
+    ($($value:ty => $data_type:ty as $param:ty),* $(,)?) => {
+        $(
+            impl<V: From<$param>> Expression<V> for $value {
+                type DataType = $data_type;
+
+                fn into_sql(self) -> SQL<V, $data_type> {
+                    SQL::param(V::from(<$param>::from(self))).cast()
+                }
+            }
+
+            impl<V: From<$param>> SQLFragment<V> for $value {
+                fn into_fragment(self) -> SQL<V> {
+                    self.into_sql().cast()
+                }
+            }
+        )*
+    };
+}
+
+impl_param_expression!(
+    i64 => i64 as i64,
+    f64 => f64 as f64,
+    bool => bool as bool,
+    String => String as String,
+    Vec<u8> => Vec<u8> as Vec<u8>,
+);
+
+impl<'a, V: From<String>> Expression<V> for &'a str {
+    type DataType = &'a str;
+
+    fn into_sql(self) -> SQL<V, &'a str> {
+        SQL::param(V::from(self.to_string())).cast()
+    }
+}
+
+impl<V: From<String>> SQLFragment<V> for &str {
+    fn into_fragment(self) -> SQL<V> {
+        self.into_sql().cast()
+    }
+}
+
+impl<'a, V: From<Vec<u8>>> Expression<V> for &'a [u8] {
+    type DataType = &'a [u8];
+
+    fn into_sql(self) -> SQL<V, &'a [u8]> {
+        SQL::param(V::from(self.to_vec())).cast()
+    }
+}
+
+impl<V: From<Vec<u8>>> SQLFragment<V> for &[u8] {
+    fn into_fragment(self) -> SQL<V> {
+        self.into_sql().cast()
+    }
+}
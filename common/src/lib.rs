@@ -1,24 +1,669 @@
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    error::Error,
+    fmt::{self, Debug, Display},
+    marker::PhantomData,
+};
+
 pub mod builders;
 pub mod expressions;
 pub mod traits;
 
+#[doc(hidden)]
+pub use macros::sql_template;
+
 pub trait ToSQL {
     fn to_sql(self) -> String;
 }
 
-pub enum SQLChunk {
-    Str(&'static str),
-    Table,
-    View,
-    AnyColumn,
-    Name,
-    Param,
-    Placeholder,
-    SQL,
+/// How a SQL dialect quotes identifiers and writes parameter placeholders.
+pub trait Dialect {
+    /// Value bound to a parameter.
+    type Param;
+
+    /// Quotes `identifier`, doubling any quote inside it.
+    fn quote_identifier(identifier: &str) -> String {
+        format!(r#""{}""#, identifier.replace('"', r#""""#))
+    }
+
+    /// Placeholder of the bound parameter at `index`, counting from 1.
+    fn placeholder(index: usize) -> String;
+
+    /// Placeholder of a parameter bound by name when the statement runs.
+    fn named_placeholder(name: &str) -> String {
+        format!(":{name}")
+    }
+
+    /// Inline literal of `value`, or `None` when the dialect has no literal for it.
+    fn literal(value: &Self::Param) -> Option<String>;
+}
+
+/// A fragment that cannot be rendered by [`SQL::build_inline`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum InlineError<V> {
+    /// A value with no literal form in the dialect, such as a NaN real.
+    Literal(V),
+    /// A named placeholder, whose value is only known when the statement runs.
+    Placeholder(&'static str),
+}
+
+impl<V: Debug> Display for InlineError<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(value) => write!(f, "{value:?} has no literal form"),
+            Self::Placeholder(name) => write!(f, "placeholder :{name} cannot be inlined"),
+        }
+    }
+}
+
+impl<V: Debug> Error for InlineError<V> {}
+
+/// One piece of a [`SQL`] fragment.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SQLChunk<V> {
+    /// SQL text, written as is.
+    Str(Cow<'static, str>),
+    /// A table name, quoted.
+    Table(&'static str),
+    /// A column, quoted and qualified with its table unless that is empty.
+    Column {
+        table: &'static str,
+        name: &'static str,
+    },
+    /// Any other identifier, quoted.
+    Name(&'static str),
+    /// A value bound as the next parameter.
+    Param(V),
+    /// A parameter bound by name when the statement runs.
+    Placeholder(&'static str),
+    /// A nested fragment, wrapped in parentheses.
+    SQL(SQL<V>),
+    /// Clauses joined by `separator`, such as `" AND "`, wrapped in parentheses.
+    Group {
+        separator: &'static str,
+        clauses: Vec<SQL<V>>,
+    },
+}
+
+/// A SQL fragment built from [`SQLChunk`]s, rendered for a [`Dialect`] into SQL text and the
+/// values bound to its placeholders. `V` is the dialect's parameter value and `T` the Rust type
+/// the fragment evaluates to.
+#[derive(Debug)]
+pub struct SQL<V, T = ()> {
+    chunks: Vec<SQLChunk<V>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<V: Clone, T> Clone for SQL<V, T> {
+    fn clone(&self) -> Self {
+        Self::from_chunks(self.chunks.clone())
+    }
+}
+
+impl<V: PartialEq, T> PartialEq for SQL<V, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.chunks == other.chunks
+    }
+}
+
+impl<V, T> Default for SQL<V, T> {
+    fn default() -> Self {
+        Self::from_chunks(vec![])
+    }
+}
+
+// Fragments start untyped; `cast` gives them the type they evaluate to.
+impl<V> SQL<V> {
+    pub fn raw(sql: impl Into<Cow<'static, str>>) -> Self {
+        Self::from_chunks(vec![SQLChunk::Str(sql.into())])
+    }
+
+    pub fn table(name: &'static str) -> Self {
+        Self::from_chunks(vec![SQLChunk::Table(name)])
+    }
+
+    pub fn column(table: &'static str, name: &'static str) -> Self {
+        Self::from_chunks(vec![SQLChunk::Column { table, name }])
+    }
+
+    pub fn name(name: &'static str) -> Self {
+        Self::from_chunks(vec![SQLChunk::Name(name)])
+    }
+
+    pub fn param(value: V) -> Self {
+        Self::from_chunks(vec![SQLChunk::Param(value)])
+    }
+
+    pub fn placeholder(name: &'static str) -> Self {
+        Self::from_chunks(vec![SQLChunk::Placeholder(name)])
+    }
+
+    /// `fragments` separated by `separator`.
+    pub fn join<U>(
+        fragments: impl IntoIterator<Item = SQL<V, U>>,
+        separator: &'static str,
+    ) -> Self {
+        let mut sql = Self::default();
+        for (index, fragment) in fragments.into_iter().enumerate() {
+            if index > 0 {
+                sql = sql.push_str(separator);
+            }
+            sql = sql.append(fragment);
+        }
+        sql
+    }
+}
+
+impl<V, T> SQL<V, T> {
+    pub fn from_chunks(chunks: Vec<SQLChunk<V>>) -> Self {
+        Self {
+            chunks,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn chunks(&self) -> &[SQLChunk<V>] {
+        &self.chunks
+    }
+
+    pub fn into_chunks(self) -> Vec<SQLChunk<V>> {
+        self.chunks
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    pub fn push(mut self, chunk: SQLChunk<V>) -> Self {
+        self.chunks.push(chunk);
+        self
+    }
+
+    pub fn push_str(self, sql: impl Into<Cow<'static, str>>) -> Self {
+        self.push(SQLChunk::Str(sql.into()))
+    }
+
+    /// Appends the chunks of `other` as they are.
+    pub fn append<U>(mut self, other: SQL<V, U>) -> Self {
+        self.chunks.extend(other.chunks);
+        self
+    }
+
+    /// Appends `other` wrapped in parentheses.
+    pub fn nest<U>(self, other: SQL<V, U>) -> Self {
+        self.push(SQLChunk::SQL(other.cast()))
+    }
+
+    /// The same fragment, evaluating to `U`.
+    pub fn cast<U>(self) -> SQL<V, U> {
+        SQL::from_chunks(self.chunks)
+    }
+
+    /// Values bound to the fragment's positional placeholders, in order.
+    pub fn params(&self) -> Vec<&V> {
+        let mut params = vec![];
+        for chunk in &self.chunks {
+            match chunk {
+                SQLChunk::Param(value) => params.push(value),
+                SQLChunk::SQL(sql) => params.extend(sql.params()),
+                SQLChunk::Group { clauses, .. } => {
+                    params.extend(clauses.iter().flat_map(SQL::params))
+                }
+                _ => {}
+            }
+        }
+        params
+    }
+
+    /// Columns the fragment refers to, as their table and name, in order.
+    pub fn columns(&self) -> Vec<(&'static str, &'static str)> {
+        let mut columns = vec![];
+        for chunk in &self.chunks {
+            match chunk {
+                SQLChunk::Column { table, name } => columns.push((*table, *name)),
+                SQLChunk::SQL(sql) => columns.extend(sql.columns()),
+                SQLChunk::Group { clauses, .. } => {
+                    columns.extend(clauses.iter().flat_map(SQL::columns))
+                }
+                _ => {}
+            }
+        }
+        columns
+    }
+
+    /// Renders the SQL text for dialect `D`, with the values bound to its placeholders.
+    pub fn build<D: Dialect<Param = V>>(self) -> (String, Vec<V>) {
+        let mut sql = String::new();
+        let mut values = Bound(vec![]);
+        let Ok(()) = self.write::<D, _>(&mut sql, &mut values);
+
+        (sql, values.0)
+    }
+
+    /// Renders the SQL text for dialect `D` with every value inlined as a literal, for DDL, where
+    /// statements cannot bind parameters. Columns render unqualified, since DDL only refers to
+    /// the columns of the table it defines.
+    pub fn build_inline<D: Dialect<Param = V>>(self) -> Result<String, InlineError<V>> {
+        let mut sql = String::new();
+        self.write::<D, _>(&mut sql, &mut Inlined)?;
+
+        Ok(sql)
+    }
+
+    fn write<D: Dialect<Param = V>, W: Values<D>>(
+        self,
+        sql: &mut String,
+        values: &mut W,
+    ) -> Result<(), W::Error> {
+        for chunk in self.chunks {
+            match chunk {
+                SQLChunk::Str(text) => sql.push_str(&text),
+                SQLChunk::Table(name) | SQLChunk::Name(name) => {
+                    sql.push_str(&D::quote_identifier(name))
+                }
+                SQLChunk::Column { table, name } => {
+                    if W::QUALIFIED && !table.is_empty() {
+                        sql.push_str(&D::quote_identifier(table));
+                        sql.push('.');
+                    }
+                    sql.push_str(&D::quote_identifier(name));
+                }
+                SQLChunk::Param(value) => sql.push_str(&values.param(value)?),
+                SQLChunk::Placeholder(name) => sql.push_str(&values.placeholder(name)?),
+                SQLChunk::SQL(nested) => {
+                    sql.push('(');
+                    nested.write::<D, W>(sql, values)?;
+                    sql.push(')');
+                }
+                SQLChunk::Group { separator, clauses } => {
+                    sql.push('(');
+                    for (index, clause) in clauses.into_iter().enumerate() {
+                        if index > 0 {
+                            sql.push_str(separator);
+                        }
+                        clause.write::<D, W>(sql, values)?;
+                    }
+                    sql.push(')');
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// What the values of a fragment turn into when it is rendered.
+trait Values<D: Dialect> {
+    type Error;
+    /// Whether columns are qualified with their table.
+    const QUALIFIED: bool;
+
+    fn param(&mut self, value: D::Param) -> Result<String, Self::Error>;
+    fn placeholder(&mut self, name: &'static str) -> Result<String, Self::Error>;
+}
+
+/// Values bound to placeholders, in order.
+struct Bound<V>(Vec<V>);
+
+impl<D: Dialect> Values<D> for Bound<D::Param> {
+    type Error = Infallible;
+    const QUALIFIED: bool = true;
+
+    fn param(&mut self, value: D::Param) -> Result<String, Infallible> {
+        self.0.push(value);
+        Ok(D::placeholder(self.0.len()))
+    }
+
+    fn placeholder(&mut self, name: &'static str) -> Result<String, Infallible> {
+        Ok(D::named_placeholder(name))
+    }
+}
+
+/// Values written as literals.
+struct Inlined;
+
+impl<D: Dialect> Values<D> for Inlined {
+    type Error = InlineError<D::Param>;
+    const QUALIFIED: bool = false;
+
+    fn param(&mut self, value: D::Param) -> Result<String, Self::Error> {
+        D::literal(&value).ok_or(InlineError::Literal(value))
+    }
+
+    fn placeholder(&mut self, name: &'static str) -> Result<String, Self::Error> {
+        Err(InlineError::Placeholder(name))
+    }
+}
+
+/// A value interpolated by [`sql!`]: fragments are inlined, columns and tables render as quoted
+/// identifiers and Rust values are bound as parameters.
+pub trait SQLFragment<V> {
+    fn into_fragment(self) -> SQL<V>;
 }
 
-// pub struct SQL<T> {
-//     decoder: dyn Any,
-// }
+impl<V, T> SQLFragment<V> for SQL<V, T> {
+    fn into_fragment(self) -> SQL<V> {
+        self.cast()
+    }
+}
+
+/// Builds the fragment of a [`sql!`] template, with `fragments` in place of its placeholders.
+#[doc(hidden)]
+pub fn template<V>(template: &'static str, fragments: Vec<SQL<V>>) -> SQL<V> {
+    let mut sql = SQL::default();
+    let mut fragments = fragments.into_iter();
+    let mut text = String::new();
+    let mut rest = template;
+    while let Some(index) = rest.find(['{', '}']) {
+        text.push_str(&rest[..index]);
+        match &rest[index..index + 2] {
+            "{}" => {
+                sql = sql
+                    .push_str(std::mem::take(&mut text))
+                    .append(fragments.next().expect("checked by `sql!`"));
+            }
+            escaped => text.push_str(&escaped[..1]),
+        }
+        rest = &rest[index + 2..];
+    }
+    text.push_str(rest);
+
+    if text.is_empty() {
+        sql
+    } else {
+        sql.push_str(text)
+    }
+}
+
+/// Builds a [`SQL`] fragment from a template, like `format!`: `{expr}` interpolates a (cloned)
+/// expression such as `{users.id}` and `{}` takes the next argument. Columns and tables become
+/// quoted identifiers, [`SQL`] fragments are inlined and Rust values are bound as parameters,
+/// never spliced into the text. A leading `<T>` gives the type the fragment evaluates to.
+///
+/// ```
+/// # use common::{sql, Dialect, SQL};
+/// # #[derive(Debug, Clone, PartialEq)]
+/// # enum Value {
+/// #     Integer(i64),
+/// # }
+/// # impl From<i64> for Value {
+/// #     fn from(value: i64) -> Self {
+/// #         Self::Integer(value)
+/// #     }
+/// # }
+/// # struct Ansi;
+/// # impl Dialect for Ansi {
+/// #     type Param = Value;
+/// #     fn placeholder(_: usize) -> String {
+/// #         "?".to_string()
+/// #     }
+/// #     fn literal(_: &Value) -> Option<String> {
+/// #         None
+/// #     }
+/// # }
+/// struct Users {
+///     id: SQL<Value, i64>,
+///     name: SQL<Value, String>,
+/// }
+///
+/// let users = Users {
+///     id: SQL::column("users", "id").cast(),
+///     name: SQL::column("users", "name").cast(),
+/// };
+/// let id = 42_i64;
+///
+/// let query: SQL<Value, String> = sql!(
+///     <String> "SELECT {users.name} FROM {} WHERE {users.id} = {id}",
+///     SQL::table("users"),
+/// );
+/// let (text, params) = query.build::<Ansi>();
+///
+/// assert_eq!(text, r#"SELECT "users"."name" FROM "users" WHERE "users"."id" = ?"#);
+/// assert_eq!(params, [Value::Integer(42)]);
+/// ```
+#[macro_export]
+macro_rules! sql {
+    (<$type:ty> $($template:tt)*) => {
+        $crate::sql!($($template)*).cast::<$type>()
+    };
+    ($($template:tt)*) => {
+        $crate::sql_template!($crate; $($template)*)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use std::marker::PhantomData;
+
+    use crate::{
+        expressions::{
+            conditions::{eq, gt},
+            Expression,
+        },
+        Dialect, InlineError, SQLChunk, SQLFragment, SQL,
+    };
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Value {
+        Integer(i64),
+        Real(f64),
+        Text(String),
+    }
+
+    impl From<i64> for Value {
+        fn from(value: i64) -> Self {
+            Self::Integer(value)
+        }
+    }
+
+    impl From<f64> for Value {
+        fn from(value: f64) -> Self {
+            Self::Real(value)
+        }
+    }
+
+    impl From<String> for Value {
+        fn from(value: String) -> Self {
+            Self::Text(value)
+        }
+    }
 
-pub fn sql<T>() {}
+    struct Question;
+
+    impl Dialect for Question {
+        type Param = Value;
+
+        fn placeholder(_: usize) -> String {
+            "?".to_string()
+        }
+
+        fn literal(value: &Value) -> Option<String> {
+            match value {
+                Value::Integer(value) => Some(value.to_string()),
+                Value::Real(value) if value.is_finite() => Some(value.to_string()),
+                Value::Real(_) => None,
+                Value::Text(value) => Some(format!("'{}'", value.replace('\'', "''"))),
+            }
+        }
+    }
+
+    struct Numbered;
+
+    impl Dialect for Numbered {
+        type Param = Value;
+
+        fn placeholder(index: usize) -> String {
+            format!("${index}")
+        }
+
+        fn literal(value: &Value) -> Option<String> {
+            Question::literal(value)
+        }
+    }
+
+    struct Column<T>(&'static str, PhantomData<T>);
+
+    impl<T> Clone for Column<T> {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
+    impl<T> Copy for Column<T> {}
+
+    impl<T> Expression<Value> for Column<T> {
+        type DataType = T;
+
+        fn into_sql(self) -> SQL<Value, T> {
+            SQL::column("users", self.0).cast()
+        }
+    }
+
+    impl<T> SQLFragment<Value> for Column<T> {
+        fn into_fragment(self) -> SQL<Value> {
+            self.into_sql().cast()
+        }
+    }
+
+    struct Users {
+        id: Column<i64>,
+        name: Column<String>,
+        score: Column<f64>,
+    }
+
+    impl Users {
+        fn new() -> Self {
+            Self {
+                id: Column("id", PhantomData),
+                name: Column("name", PhantomData),
+                score: Column("score", PhantomData),
+            }
+        }
+    }
+
+    #[test]
+    fn sql_chunks() {
+        let users = Users::new();
+        let sql: SQL<Value, bool> = SQL::raw("SELECT ")
+            .append(SQL::name(r#"odd "name""#))
+            .push_str(" FROM ")
+            .append(SQL::table("users"))
+            .push_str(" WHERE ")
+            .append(eq(users.id, 1))
+            .push_str(" AND ")
+            .nest(eq(users.name, "ann"))
+            .push_str(" AND ")
+            .append(SQL::column("", "score"))
+            .push_str(" > ")
+            .append(SQL::placeholder("min_score"))
+            .cast();
+
+        assert_eq!(
+            sql.params(),
+            [&Value::Integer(1), &Value::Text("ann".to_string())]
+        );
+        assert_eq!(
+            sql.columns(),
+            [("users", "id"), ("users", "name"), ("", "score")]
+        );
+        assert!(matches!(sql.chunks()[0], SQLChunk::Str(_)));
+        assert_eq!(
+            sql.clone().build_inline::<Question>(),
+            Err(InlineError::Placeholder("min_score"))
+        );
+        assert_eq!(
+            gt(users.score, 0.5).build_inline::<Question>(),
+            Ok(r#""score" > 0.5"#.to_string())
+        );
+        assert!(matches!(
+            gt(users.score, f64::NAN).build_inline::<Question>(),
+            Err(InlineError::Literal(Value::Real(value))) if value.is_nan()
+        ));
+        assert_eq!(
+            sql.clone().build::<Question>().0,
+            concat!(
+                r#"SELECT "odd ""name""" FROM "users" WHERE "users"."id" = ? "#,
+                r#"AND ("users"."name" = ?) AND "score" > :min_score"#
+            )
+        );
+        assert_eq!(
+            sql.build::<Numbered>(),
+            (
+                concat!(
+                    r#"SELECT "odd ""name""" FROM "users" WHERE "users"."id" = $1 "#,
+                    r#"AND ("users"."name" = $2) AND "score" > :min_score"#
+                )
+                .to_string(),
+                vec![Value::Integer(1), Value::Text("ann".to_string())]
+            )
+        );
+    }
+
+    #[test]
+    fn sql_macro() {
+        let users = Users::new();
+        let name = "x' OR '1' = '1";
+
+        let query = sql!(
+            <String> "SELECT {} FROM {} WHERE {} AND {} = {} AND {{literal}} <> {}",
+            users.name,
+            SQL::table("users"),
+            gt(users.id, 1),
+            users.name,
+            name,
+            SQL::raw("'}'"),
+        );
+
+        assert_eq!(
+            query.build::<Question>(),
+            (
+                concat!(
+                    r#"SELECT "users"."name" FROM "users" "#,
+                    r#"WHERE "users"."id" > ? AND "users"."name" = ? AND {literal} <> '}'"#
+                )
+                .to_string(),
+                vec![Value::Integer(1), Value::Text(name.to_string())]
+            )
+        );
+        assert_eq!(sql!("SELECT 1").build::<Question>().0, "SELECT 1");
+    }
+
+    #[test]
+    fn sql_macro_interpolation() {
+        let users = Users::new();
+        let min_id = 1;
+        let names = ["ann", "bob"];
+
+        let query: SQL<Value, i64> = sql!(
+            <i64> "SELECT count(*) FROM {} WHERE {users.id} > { min_id } AND {users.name} IN ({names[0]}, {})",
+            SQL::table("users"),
+            names[1],
+        );
+
+        assert_eq!(
+            query.build::<Numbered>(),
+            (
+                concat!(
+                    r#"SELECT count(*) FROM "users" WHERE "users"."id" > $1 "#,
+                    r#"AND "users"."name" IN ($2, $3)"#
+                )
+                .to_string(),
+                vec![
+                    Value::Integer(1),
+                    Value::Text("ann".to_string()),
+                    Value::Text("bob".to_string())
+                ]
+            )
+        );
+        // interpolated expressions are cloned, so they stay usable afterwards
+        let nickname = "annie".to_string();
+        assert_eq!(
+            sql!("{users.name} = {nickname}").build::<Question>(),
+            (
+                r#""users"."name" = ?"#.to_string(),
+                vec![Value::Text(nickname)]
+            )
+        );
+    }
+}
@@ -18,6 +18,14 @@ pub trait DefaultFn: Clone + Copy + Default {
     const HAS_DEFAULT_FN: bool;
 }
 
+pub trait UpdateFn: Clone + Copy + Default {
+    const HAS_UPDATE_FN: bool;
+}
+
+pub trait ForeignKey: Clone + Copy + Default {
+    const HAS_REFERENCE: bool;
+}
+
 pub trait SQLPrimary: Clone {
     type Value;
     fn primary(self) -> Self::Value;
@@ -41,6 +49,13 @@ pub trait SQLDefault: Clone {
     fn default(self, value: Self::DataType) -> Self::Value;
 }
 
+pub trait SQLDefaultSQL: Clone {
+    type Expression;
+    type Value;
+
+    fn default_sql(self, sql: Self::Expression) -> Self::Value;
+}
+
 pub trait SQLDefaultFn: Clone {
     type DataType;
     type Error;
@@ -51,7 +66,26 @@ pub trait SQLDefaultFn: Clone {
         F: (Fn() -> Result<Self::DataType, Self::Error>) + Clone;
 }
 
+pub trait SQLOnUpdateSQL: Clone {
+    type Expression;
+    type Value;
+
+    fn on_update_sql(self, sql: Self::Expression) -> Self::Value;
+}
+
+pub trait SQLOnUpdateFn: Clone {
+    type DataType;
+    type Error;
+    type Value<F: Fn() -> Result<Self::DataType, Self::Error> + Clone>;
+
+    fn on_update_fn<F>(self, fun: F) -> Self::Value<F>
+    where
+        F: (Fn() -> Result<Self::DataType, Self::Error>) + Clone;
+}
+
 pub trait ColumnBuilder {
-    fn name(&self) -> &str;
+    fn name(&self) -> &'static str;
+    fn table(&self) -> &'static str;
+    fn in_table(self, table: &'static str) -> Self;
     fn build(self) -> Self;
 }
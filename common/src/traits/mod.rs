@@ -1,4 +1,10 @@
+use std::time::SystemTime;
+
 mod column;
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be compared with `{Rhs}`",
+    label = "compared with `{Rhs}` here"
+)]
 pub trait Comparable<Rhs: ?Sized> {}
 pub use column::*;
 
@@ -8,18 +14,35 @@ impl Comparable<&i64> for i64 {}
 impl Comparable<f64> for i64 {}
 impl Comparable<&f64> for i64 {}
 
+impl Comparable<i128> for i128 {}
+impl Comparable<&i128> for i128 {}
+
+impl Comparable<u128> for u128 {}
+impl Comparable<&u128> for u128 {}
+
+impl Comparable<bool> for bool {}
+impl Comparable<&bool> for bool {}
+
+impl Comparable<SystemTime> for SystemTime {}
+impl Comparable<&SystemTime> for SystemTime {}
+
+#[cfg(feature = "chrono")]
+impl Comparable<chrono::DateTime<chrono::Utc>> for chrono::DateTime<chrono::Utc> {}
+#[cfg(feature = "chrono")]
+impl Comparable<&chrono::DateTime<chrono::Utc>> for chrono::DateTime<chrono::Utc> {}
+
 impl Comparable<f64> for f64 {}
 impl Comparable<&f64> for f64 {}
 
 impl Comparable<Self> for String {}
-impl<'a> Comparable<String> for &'a String {}
-impl<'a> Comparable<Self> for &'a String {}
+impl Comparable<String> for &String {}
+impl Comparable<Self> for &String {}
 
 impl Comparable<Self> for Vec<u8> {}
-impl<'a> Comparable<Self> for &'a Vec<u8> {}
+impl Comparable<Self> for &Vec<u8> {}
 
-impl<'a> Comparable<&'a [u8]> for Vec<u8> {}
-impl<'a> Comparable<Self> for &'a [u8] {}
+impl Comparable<&[u8]> for Vec<u8> {}
+impl Comparable<Self> for &[u8] {}
 
-impl<'a> Comparable<&'a str> for String {}
+impl Comparable<&str> for String {}
 impl<'a> Comparable<&'a str> for &'a str {}
@@ -0,0 +1,141 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Group, Span, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Expr, LitStr, Path, Token,
+};
+
+/// Input of [`sql_template!`]: the path of the `common` crate, then the arguments of `sql!`.
+struct Template {
+    krate: Path,
+    template: LitStr,
+    args: Punctuated<Expr, Token![,]>,
+}
+
+impl Parse for Template {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.call(Path::parse_mod_style)?;
+        input.parse::<Token![;]>()?;
+        let template = input.parse()?;
+        let args = match input.parse::<Option<Token![,]>>()? {
+            Some(_) => Punctuated::parse_terminated(input)?,
+            None => Punctuated::new(),
+        };
+
+        Ok(Self {
+            krate,
+            template,
+            args,
+        })
+    }
+}
+
+/// Gives `tokens` the span of the template they were parsed from, so the names in them resolve
+/// where `sql!` is called.
+fn respan(tokens: TokenStream2, span: Span) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut inner = Group::new(group.delimiter(), respan(group.stream(), span));
+                inner.set_span(span);
+                token = TokenTree::Group(inner);
+            }
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
+
+/// Rewrites a `sql!` template so every placeholder is a positional `{}`, pairing each with the
+/// expression it interpolates: the next argument for `{}`, the expression itself for `{expr}`.
+fn placeholders(template: &LitStr, args: Vec<Expr>) -> syn::Result<(String, Vec<TokenStream2>)> {
+    let source = template.value();
+    let mut text = String::with_capacity(source.len());
+    let mut fragments = Vec::new();
+    let mut args = args.into_iter();
+    let mut rest = source.as_str();
+
+    while let Some(index) = rest.find(['{', '}']) {
+        text.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            text.push_str(&rest[..2]);
+            rest = &rest[2..];
+            continue;
+        }
+
+        let end = match (rest.starts_with('{'), rest.find('}')) {
+            (true, Some(end)) => end,
+            _ => {
+                return Err(syn::Error::new(
+                    template.span(),
+                    "unescaped brace in `sql!` template, write `{{` or `}}`",
+                ))
+            }
+        };
+
+        let name = rest[1..end].trim();
+        let fragment = if name.is_empty() {
+            let arg = args.next().ok_or_else(|| {
+                syn::Error::new(
+                    template.span(),
+                    "`sql!` takes one argument per `{}` placeholder",
+                )
+            })?;
+            quote!(#arg)
+        } else {
+            let expr = syn::parse_str::<TokenStream2>(name)
+                .and_then(|tokens| syn::parse2::<Expr>(respan(tokens, template.span())))
+                .map_err(|error| {
+                    syn::Error::new(
+                        template.span(),
+                        format!("invalid expression `{name}` in `sql!` template: {error}"),
+                    )
+                })?;
+            quote!(::core::clone::Clone::clone(&(#expr)))
+        };
+
+        text.push_str("{}");
+        fragments.push(fragment);
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+
+    if args.next().is_some() {
+        return Err(syn::Error::new(
+            template.span(),
+            "`sql!` takes one argument per `{}` placeholder",
+        ));
+    }
+
+    Ok((text, fragments))
+}
+
+/// Expands `sql!` once its type annotation is stripped; not meant to be called directly.
+#[proc_macro]
+pub fn sql_template(input: TokenStream) -> TokenStream {
+    let Template {
+        krate,
+        template,
+        args,
+    } = parse_macro_input!(input as Template);
+
+    match placeholders(&template, args.into_iter().collect()) {
+        Ok((text, fragments)) => {
+            let text = LitStr::new(&text, template.span());
+            quote! {
+                #krate::template(
+                    #text,
+                    ::std::vec![#( #krate::SQLFragment::into_fragment(#fragments) ),*],
+                )
+            }
+            .into()
+        }
+        Err(error) => error.to_compile_error().into(),
+    }
+}
@@ -6,17 +6,21 @@ use common::{
 use crate::common::Any;
 
 use super::{
-    integer::NotAutoIncremented, DefaultFnNotSet, DefaultNotSet, NotPrimary, NotUnique, Nullable,
-    SQLiteColumn,
+    integer::NotAutoIncremented, DefaultFnNotSet, DefaultNotSet, GeneratedNotSet, NotPrimary,
+    NotUnique, Nullable, ReferenceNotSet, SQLiteColumn, UpdateFnNotSet,
 };
 
-type SQLiteAnyColumn<
+pub type SQLiteAnyColumn<
     TPrimary = NotPrimary,
     TNotNull = Nullable,
     TUnique = NotUnique,
     TDefault = DefaultNotSet,
     TDefaultFn = DefaultFnNotSet,
     TFunc = fn() -> Result<Any, std::fmt::Error>,
+    TUpdateFn = UpdateFnNotSet,
+    TUpdateFunc = fn() -> Result<Any, std::fmt::Error>,
+    TReference = ReferenceNotSet,
+    TGenerated = GeneratedNotSet,
 > = SQLiteColumn<
     Any,
     Any,
@@ -28,9 +32,13 @@ type SQLiteAnyColumn<
     TDefault,
     TDefaultFn,
     TFunc,
+    TUpdateFn,
+    TUpdateFunc,
+    TReference,
+    TGenerated,
 >;
 
-pub(crate) fn any(name: &'static str) -> SQLiteAnyColumn {
+pub fn any(name: &'static str) -> SQLiteAnyColumn {
     SQLiteAnyColumn {
         base: ColumnBaseBuilder {
             name,
@@ -60,19 +68,32 @@ impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Compar
 
 #[cfg(test)]
 mod test {
+    use common::traits::{SQLDefault, SQLNotNull, SQLUnique};
 
-    // #[test]
-    // fn builder() {
-    //     let str = 12.0;
-    //     let int = real("id").primary().not_null().default(str);
+    use crate::{common::Any, traits::table::SQLiteDefinition};
 
-    //     std::thread::spawn(move || {
-    //         let int = int;
-    //         assert_eq!(int.base.default, Some(12.0));
-    //     });
+    use super::any;
 
-    // .autoincrement()
-    // .not_null()
-    // .default(42);
-    // }
+    #[test]
+    fn builder() {
+        let column = any("value").not_null().default(Any::Real(12.0));
+
+        assert!(matches!(column.default, Some(Any::Real(value)) if value == 12.0));
+    }
+
+    #[test]
+    fn to_sql() {
+        assert_eq!(any("value").to_sql().unwrap(), r#""value" ANY"#);
+        assert_eq!(
+            any("value").unique("value_unique").to_sql().unwrap(),
+            r#""value" ANY CONSTRAINT "value_unique" UNIQUE"#
+        );
+        assert_eq!(
+            any("value")
+                .default(Any::Text("none".into()))
+                .to_sql()
+                .unwrap(),
+            r#""value" ANY DEFAULT 'none'"#
+        );
+    }
 }
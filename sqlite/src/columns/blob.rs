@@ -1,26 +1,170 @@
 use common::{
     builders::column::ColumnBaseBuilder,
-    traits::{Comparable, DefaultFn, DefaultValue, NotNull, PrimaryKey, Unique},
+    traits::{
+        Comparable, DefaultFn, DefaultValue, ForeignKey, NotNull, PrimaryKey, Unique, UpdateFn,
+    },
 };
 
-use crate::common::Blob;
+use crate::{
+    common::Blob,
+    expressions::SQLiteJSONExpression,
+    traits::column::{Generated, SQLiteDataMode},
+};
 
 use super::{
-    integer::NotAutoIncremented, DefaultFnNotSet, DefaultNotSet, NotPrimary, NotUnique, Nullable,
-    SQLiteColumn,
+    integer::NotAutoIncremented, DefaultFnNotSet, DefaultNotSet, GeneratedNotSet, NotPrimary,
+    NotUnique, Nullable, ReferenceNotSet, SQLiteColumn, UpdateFnNotSet,
 };
 
-type SQLiteBlobColumn<
+/// Mode of a blob column, with the Rust type its columns hold unless one is given explicitly.
+pub trait BlobMode: Copy + Default {
+    type DataType: Clone;
+}
+
+/// Modes of blob columns. Each is a type, so the mode picks how the column's Rust type is stored,
+/// e.g. `blob("balance", SQLiteBlobMode::BigInt)` holds an `i128`.
+#[allow(non_snake_case)]
+pub mod SQLiteBlobMode {
+    use common::SQL;
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use crate::{
+        common::{Blob, SQLiteDecodeError, SQLiteEncodeError, SQLiteValue},
+        traits::column::SQLiteDataMode,
+    };
+
+    use super::{BlobMode, SQLiteBigInt};
+
+    /// Raw bytes.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Buffer;
+
+    /// Any `serde` type, stored as SQLite's binary JSON (JSONB, SQLite 3.45+) so the JSON
+    /// functions accept it; `serde_json::Value` unless given explicitly. Values are bound as JSON
+    /// text converted by `jsonb(?)`, and read back as text through `json(column)`.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct JSON;
+
+    /// `i128` or `u128`, stored as 16 big-endian bytes. The sign bit of an `i128` is flipped, so
+    /// blobs compare in the same order as the integers.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct BigInt;
+
+    fn blob(value: SQLiteValue) -> Result<Blob, SQLiteDecodeError> {
+        match value {
+            SQLiteValue::Blob(value) => Ok(value),
+            value => Err(SQLiteDecodeError::new("BLOB", value)),
+        }
+    }
+
+    impl BlobMode for Buffer {
+        type DataType = Blob;
+    }
+
+    impl SQLiteDataMode<Blob> for Buffer {
+        fn to_sql_value(value: Blob) -> Result<SQLiteValue, SQLiteEncodeError> {
+            Ok(SQLiteValue::Blob(value))
+        }
+
+        fn from_sql_value(value: SQLiteValue) -> Result<Blob, SQLiteDecodeError> {
+            blob(value)
+        }
+    }
+
+    impl BlobMode for JSON {
+        type DataType = serde_json::Value;
+    }
+
+    impl<T: Serialize + DeserializeOwned + Clone> SQLiteDataMode<T> for JSON {
+        fn to_sql_value(value: T) -> Result<SQLiteValue, SQLiteEncodeError> {
+            serde_json::to_string(&value)
+                .map(SQLiteValue::Text)
+                .map_err(|error| SQLiteEncodeError::new("JSON text", error))
+        }
+
+        fn from_sql_value(value: SQLiteValue) -> Result<T, SQLiteDecodeError> {
+            match value {
+                SQLiteValue::Text(json) => serde_json::from_str(&json)
+                    .map_err(|_| SQLiteDecodeError::new("JSON text", SQLiteValue::Text(json))),
+                value => Err(SQLiteDecodeError::new("JSON text", value)),
+            }
+        }
+
+        fn write_sql(value: SQLiteValue) -> SQL<SQLiteValue> {
+            SQL::raw("jsonb(").append(SQL::param(value)).push_str(")")
+        }
+
+        fn read_sql(column: SQL<SQLiteValue>) -> SQL<SQLiteValue> {
+            SQL::raw("json(").append(column).push_str(")")
+        }
+    }
+
+    impl BlobMode for BigInt {
+        type DataType = i128;
+    }
+
+    impl<T: SQLiteBigInt> SQLiteDataMode<T> for BigInt {
+        fn to_sql_value(value: T) -> Result<SQLiteValue, SQLiteEncodeError> {
+            Ok(SQLiteValue::Blob(value.to_bytes().to_vec()))
+        }
+
+        fn from_sql_value(value: SQLiteValue) -> Result<T, SQLiteDecodeError> {
+            let value = blob(value)?;
+
+            match value.as_slice().try_into() {
+                Ok(bytes) => Ok(T::from_bytes(bytes)),
+                Err(_) => Err(SQLiteDecodeError::new(
+                    "16-byte BLOB",
+                    SQLiteValue::Blob(value),
+                )),
+            }
+        }
+    }
+}
+
+/// Integer stored by `SQLiteBlobMode::BigInt`, as the 16 bytes its blob holds.
+pub trait SQLiteBigInt: Clone {
+    fn to_bytes(self) -> [u8; 16];
+    fn from_bytes(bytes: [u8; 16]) -> Self;
+}
+
+impl SQLiteBigInt for i128 {
+    fn to_bytes(self) -> [u8; 16] {
+        ((self as u128) ^ (1 << 127)).to_be_bytes()
+    }
+
+    fn from_bytes(bytes: [u8; 16]) -> Self {
+        (u128::from_be_bytes(bytes) ^ (1 << 127)) as i128
+    }
+}
+
+impl SQLiteBigInt for u128 {
+    fn to_bytes(self) -> [u8; 16] {
+        self.to_be_bytes()
+    }
+
+    fn from_bytes(bytes: [u8; 16]) -> Self {
+        u128::from_be_bytes(bytes)
+    }
+}
+
+pub type SQLiteBlobColumn<
+    TMode = SQLiteBlobMode::Buffer,
+    TData = <TMode as BlobMode>::DataType,
     TPrimary = NotPrimary,
     TNotNull = Nullable,
     TUnique = NotUnique,
     TDefault = DefaultNotSet,
     TDefaultFn = DefaultFnNotSet,
-    TFunc = fn() -> Result<Vec<u8>, std::fmt::Error>,
+    TFunc = fn() -> Result<TData, std::fmt::Error>,
+    TUpdateFn = UpdateFnNotSet,
+    TUpdateFunc = fn() -> Result<TData, std::fmt::Error>,
+    TReference = ReferenceNotSet,
+    TGenerated = GeneratedNotSet,
 > = SQLiteColumn<
-    Vec<u8>,
+    TData,
     Blob,
-    (),
+    TMode,
     TPrimary,
     TNotNull,
     TUnique,
@@ -28,64 +172,315 @@ type SQLiteBlobColumn<
     TDefault,
     TDefaultFn,
     TFunc,
+    TUpdateFn,
+    TUpdateFunc,
+    TReference,
+    TGenerated,
 >;
 
-pub(crate) fn blob(name: &'static str) -> SQLiteBlobColumn {
+/// Blob column in `mode`, holding `T`; `T` is inferred when the mode stores a single type.
+pub fn blob<T: Clone, M: BlobMode + SQLiteDataMode<T>>(
+    name: &'static str,
+    mode: M,
+) -> SQLiteBlobColumn<M, T> {
     SQLiteBlobColumn {
         base: ColumnBaseBuilder {
             name,
+            mode,
             ..Default::default()
         },
         ..Default::default()
     }
 }
-impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Comparable<Vec<u8>>
-    for SQLiteBlobColumn<P, N, U, D, F>
+impl<M: BlobMode, P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn>
+    Comparable<Vec<u8>> for SQLiteBlobColumn<M, Vec<u8>, P, N, U, D, F>
+{
+}
+impl<M: BlobMode, P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn>
+    Comparable<&Vec<u8>> for SQLiteBlobColumn<M, Vec<u8>, P, N, U, D, F>
+{
+}
+
+impl<M: BlobMode, P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn>
+    Comparable<&[u8]> for SQLiteBlobColumn<M, Vec<u8>, P, N, U, D, F>
+{
+}
+
+impl<M: BlobMode, P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn>
+    Comparable<SQLiteBlobColumn<M, Vec<u8>, P, N, U, D, F>> for Vec<u8>
+{
+}
+
+impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Comparable<i128>
+    for SQLiteBlobColumn<SQLiteBlobMode::BigInt, i128, P, N, U, D, F>
 {
 }
-impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Comparable<&Vec<u8>>
-    for SQLiteBlobColumn<P, N, U, D, F>
+
+impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Comparable<u128>
+    for SQLiteBlobColumn<SQLiteBlobMode::BigInt, u128, P, N, U, D, F>
 {
 }
 
-impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Comparable<&[u8]>
-    for SQLiteBlobColumn<P, N, U, D, F>
+impl<
+        M: BlobMode,
+        T: Clone,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        D: DefaultValue,
+        F: DefaultFn,
+    > Comparable<Self> for SQLiteBlobColumn<M, T, P, N, U, D, F>
 {
 }
 
-impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn>
-    Comparable<SQLiteBlobColumn<P, N, U, D, F>> for Vec<u8>
+impl<
+        M: BlobMode,
+        T: Clone,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        D: DefaultValue,
+        F: DefaultFn,
+    > Comparable<Self> for &SQLiteBlobColumn<M, T, P, N, U, D, F>
 {
 }
 
-impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Comparable<Self>
-    for SQLiteBlobColumn<P, N, U, D, F>
+impl<
+        T: Clone,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        D: DefaultValue,
+        F: DefaultFn,
+        Fun: Fn() -> Result<T, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<T, std::fmt::Error> + Clone,
+        R: ForeignKey,
+        G: Generated,
+    > SQLiteJSONExpression
+    for SQLiteBlobColumn<SQLiteBlobMode::JSON, T, P, N, U, D, F, Fun, UF, UFun, R, G>
 {
 }
 
-impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Comparable<Self>
-    for &SQLiteBlobColumn<P, N, U, D, F>
+impl<
+        T: Clone,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        D: DefaultValue,
+        F: DefaultFn,
+        Fun: Fn() -> Result<T, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<T, std::fmt::Error> + Clone,
+        R: ForeignKey,
+        G: Generated,
+    > SQLiteJSONExpression
+    for &SQLiteBlobColumn<SQLiteBlobMode::JSON, T, P, N, U, D, F, Fun, UF, UFun, R, G>
 {
 }
 
 #[cfg(test)]
 mod test {
-    use super::blob;
-    use core::panic;
+    use common::traits::{ColumnBuilder, SQLDefault, SQLDefaultFn, SQLNotNull, SQLPrimary};
+
+    use serde_json::json;
 
-    // #[test]
-    // fn builder() {
-    //     let b = blob("id").primary().not_null().default(vec![]);
+    use crate::{
+        common::{SQLiteDialect, SQLiteValue},
+        prelude::*,
+        traits::{
+            column::{SQLiteColumnData, SQLiteDataMode},
+            table::SQLiteDefinition,
+        },
+    };
+
+    use super::{blob, SQLiteBlobMode};
+
+    #[test]
+    fn builder() {
+        let column = blob("id", SQLiteBlobMode::Buffer)
+            .primary()
+            .not_null()
+            .default(vec![]);
 
-    //     std::thread::spawn(move || {
-    //         let b = b;
-    //         assert_eq!(b.base.default, Some(vec![]));
-    //     });
+        std::thread::spawn(move || {
+            let column = column;
+            assert_eq!(column.default, Some(vec![]));
+        })
+        .join()
+        .unwrap();
 
-    //     let b = blob("id").default_fn(|| Ok(vec![]));
+        let column = blob("id", SQLiteBlobMode::Buffer).default_fn(|| Ok(vec![1, 2]));
+
+        assert_eq!(column.default_fn.map(|f| f()), Some(Ok(vec![1, 2])));
+    }
 
-    //     // .autoincrement()
-    //     // .not_null()
-    //     // .default(42);
-    // }
+    #[test]
+    fn to_sql() {
+        assert_eq!(
+            blob("payload", SQLiteBlobMode::Buffer).to_sql().unwrap(),
+            r#""payload" BLOB"#
+        );
+        assert_eq!(
+            blob("payload", SQLiteBlobMode::Buffer)
+                .not_null()
+                .to_sql()
+                .unwrap(),
+            r#""payload" BLOB NOT NULL"#
+        );
+        assert_eq!(
+            blob("payload", SQLiteBlobMode::Buffer)
+                .default(vec![0xDE, 0xAD])
+                .to_sql()
+                .unwrap(),
+            r#""payload" BLOB DEFAULT X'DEAD'"#
+        );
+    }
+
+    #[test]
+    fn big_int() {
+        let values = [i128::MIN, -1, 0, 1, i128::MAX];
+        let blobs: Vec<SQLiteValue> = values
+            .iter()
+            .map(|&value| SQLiteBlobMode::BigInt::to_sql_value(value).unwrap())
+            .collect();
+
+        assert!(blobs.windows(2).all(|pair| match pair {
+            [SQLiteValue::Blob(a), SQLiteValue::Blob(b)] => a < b,
+            _ => false,
+        }));
+        for (value, blob) in values.into_iter().zip(blobs) {
+            assert_eq!(SQLiteBlobMode::BigInt::from_sql_value(blob), Ok(value));
+        }
+        assert_eq!(
+            SQLiteBlobMode::BigInt::to_sql_value(u128::MAX),
+            Ok(SQLiteValue::Blob(vec![0xFF; 16]))
+        );
+        assert!(
+            <SQLiteBlobMode::BigInt as SQLiteDataMode<u128>>::from_sql_value(SQLiteValue::Blob(
+                vec![0; 8]
+            ))
+            .is_err()
+        );
+        assert_eq!(
+            blob("balance", SQLiteBlobMode::BigInt)
+                .default(1i128)
+                .to_sql()
+                .unwrap(),
+            r#""balance" BLOB DEFAULT X'80000000000000000000000000000001'"#
+        );
+    }
+
+    #[test]
+    fn json() {
+        let value = json!({"a": [1, true, null, "x", 1.5]});
+        let text = SQLiteBlobMode::JSON::to_sql_value(value.clone()).unwrap();
+        let settings =
+            blob::<serde_json::Value, _>("settings", SQLiteBlobMode::JSON).in_table("users");
+
+        assert_eq!(
+            text,
+            SQLiteValue::Text(r#"{"a":[1,true,null,"x",1.5]}"#.to_string())
+        );
+        assert_eq!(SQLiteBlobMode::JSON::from_sql_value(text), Ok(value));
+        assert!(
+            <SQLiteBlobMode::JSON as SQLiteDataMode<serde_json::Value>>::from_sql_value(
+                SQLiteValue::Blob(vec![0x13])
+            )
+            .is_err()
+        );
+        assert_eq!(
+            settings.read_sql().build::<SQLiteDialect>().0,
+            r#"json("users"."settings")"#
+        );
+        assert_eq!(
+            blob("settings", SQLiteBlobMode::JSON)
+                .default(json!({"theme": "dark"}))
+                .to_sql()
+                .unwrap(),
+            r#""settings" BLOB DEFAULT (jsonb('{"theme":"dark"}'))"#
+        );
+    }
+
+    #[test]
+    fn blob_modes() {
+        use common::expressions::conditions::gt;
+        use serde::{Deserialize, Serialize};
+
+        use crate::common::Integer;
+        use crate::expressions::{big_int, json_extract};
+
+        #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+        pub struct Settings {
+            theme: String,
+            volume: i64,
+        }
+
+        crate::sqlite_table!("wallets", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            balance: blob("balance", SQLiteBlobMode::BigInt).not_null(),
+            settings: blob::<Settings>("settings", SQLiteBlobMode::JSON),
+        }, |table| [
+            check("wallets_solvent", gt(&table.balance, big_int(-1i128))),
+            check("wallets_volume", gt(json_extract::<Integer, _>(&table.settings, "$.volume"), 0)),
+        ]);
+
+        let wallets = Wallets::new();
+        let settings = Settings {
+            theme: "dark".to_string(),
+            volume: 7,
+        };
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&wallets.clone().to_sql().unwrap(), ())
+            .unwrap();
+
+        let insert_wallet = |id: Integer, balance: i128, settings: Settings| {
+            let statement = insert(&wallets)
+                .values(InsertWallets {
+                    id: Some(id),
+                    balance: Some(balance),
+                    settings: Some(settings),
+                })
+                .unwrap();
+
+            conn.execute(
+                &statement.clone().to_sql(),
+                rusqlite::params_from_iter(statement.params()),
+            )
+        };
+
+        insert_wallet(1, i128::MAX, settings.clone()).unwrap();
+        assert!(insert_wallet(2, -5, settings.clone()).is_err());
+        assert!(insert_wallet(
+            3,
+            0,
+            Settings {
+                volume: 0,
+                ..settings.clone()
+            }
+        )
+        .is_err());
+
+        let (balance, json, stored): (Vec<u8>, String, String) = conn
+            .query_row(
+                &format!(
+                    r#"SELECT "balance", {}, typeof("settings") FROM "wallets""#,
+                    wallets.settings.read_sql().build::<SQLiteDialect>().0
+                ),
+                (),
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+
+        assert_eq!(
+            SQLiteBlobMode::BigInt::from_sql_value(SQLiteValue::Blob(balance)),
+            Ok(i128::MAX)
+        );
+        assert_eq!(stored, "blob");
+        assert_eq!(
+            SQLiteBlobMode::JSON::from_sql_value(SQLiteValue::Text(json)),
+            Ok(settings)
+        );
+    }
 }
@@ -0,0 +1,296 @@
+use std::{fmt, marker::PhantomData};
+
+use common::builders::column::ColumnBaseBuilder;
+
+use crate::{
+    common::{SQLiteDecodeError, SQLiteEncodeError, SQLiteValue},
+    traits::column::{SQLiteColumnType, SQLiteDataMode},
+};
+
+use super::{
+    integer::NotAutoIncremented, DefaultFnNotSet, DefaultNotSet, GeneratedNotSet, NotPrimary,
+    NotUnique, Nullable, ReferenceNotSet, SQLiteColumn, UpdateFnNotSet,
+};
+
+/// A column type defined outside this crate, such as a UUID or a decimal: the type name its
+/// columns are declared with, the Rust type they hold and how values convert to and from what
+/// SQLite stores. Usually implemented on a unit struct passed to [`custom_type`].
+///
+/// Comparing the column in expressions needs `DataType: Comparable<DataType>`.
+pub trait SQLiteCustomType: Default + Clone {
+    type DataType: Clone;
+
+    /// Declared type of the column, e.g. `TEXT` or `NUMERIC(10, 2)`.
+    const SQL_TYPE: &'static str;
+
+    /// Converts a value to what SQLite stores, failing when the value cannot be stored.
+    fn to_driver(value: Self::DataType) -> Result<SQLiteValue, SQLiteEncodeError>;
+
+    /// Reads a stored value back, failing when it is not one `to_driver` writes.
+    fn from_driver(value: SQLiteValue) -> Result<Self::DataType, SQLiteDecodeError>;
+}
+
+/// Column type and data mode of columns of the custom type `T`.
+pub struct Custom<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for Custom<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Clone for Custom<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Custom<T> {}
+
+impl<T> fmt::Debug for Custom<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Custom<{}>", std::any::type_name::<T>())
+    }
+}
+
+impl<T: SQLiteCustomType> SQLiteColumnType for Custom<T> {
+    const SQL_TYPE: &'static str = T::SQL_TYPE;
+}
+
+impl<T: SQLiteCustomType> SQLiteDataMode<T::DataType> for Custom<T> {
+    fn to_sql_value(value: T::DataType) -> Result<SQLiteValue, SQLiteEncodeError> {
+        T::to_driver(value)
+    }
+
+    fn from_sql_value(value: SQLiteValue) -> Result<T::DataType, SQLiteDecodeError> {
+        T::from_driver(value)
+    }
+}
+
+pub type SQLiteCustomColumn<
+    T,
+    TPrimary = NotPrimary,
+    TNotNull = Nullable,
+    TUnique = NotUnique,
+    TDefault = DefaultNotSet,
+    TDefaultFn = DefaultFnNotSet,
+    TFunc = fn() -> Result<<T as SQLiteCustomType>::DataType, std::fmt::Error>,
+    TUpdateFn = UpdateFnNotSet,
+    TUpdateFunc = fn() -> Result<<T as SQLiteCustomType>::DataType, std::fmt::Error>,
+    TReference = ReferenceNotSet,
+    TGenerated = GeneratedNotSet,
+> = SQLiteColumn<
+    <T as SQLiteCustomType>::DataType,
+    Custom<T>,
+    Custom<T>,
+    TPrimary,
+    TNotNull,
+    TUnique,
+    NotAutoIncremented,
+    TDefault,
+    TDefaultFn,
+    TFunc,
+    TUpdateFn,
+    TUpdateFunc,
+    TReference,
+    TGenerated,
+>;
+
+/// Column of the custom type `T`, e.g. `custom_type("id", Uuid)`.
+pub fn custom_type<T: SQLiteCustomType>(
+    name: &'static str,
+    _custom_type: T,
+) -> SQLiteCustomColumn<T> {
+    SQLiteCustomColumn {
+        base: ColumnBaseBuilder {
+            name,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::traits::{SQLDefault, SQLNotNull, SQLPrimary};
+
+    use crate::{
+        common::{SQLiteDecodeError, SQLiteDefinitionError, SQLiteEncodeError, SQLiteValue},
+        prelude::*,
+        traits::{column::SQLiteDataMode, table::SQLiteDefinition},
+    };
+
+    use super::{custom_type, Custom, SQLiteCustomType};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(Debug, Default, Clone)]
+    struct GeoPoint;
+
+    impl SQLiteCustomType for GeoPoint {
+        type DataType = Point;
+
+        const SQL_TYPE: &'static str = "POINT";
+
+        fn to_driver(value: Point) -> Result<SQLiteValue, SQLiteEncodeError> {
+            if !value.x.is_finite() || !value.y.is_finite() {
+                return Err(SQLiteEncodeError::new(
+                    "TEXT point",
+                    "coordinates must be finite",
+                ));
+            }
+
+            Ok(SQLiteValue::Text(format!("{},{}", value.x, value.y)))
+        }
+
+        fn from_driver(value: SQLiteValue) -> Result<Point, SQLiteDecodeError> {
+            let SQLiteValue::Text(text) = &value else {
+                return Err(SQLiteDecodeError::new("TEXT point", value));
+            };
+            let point = text.split_once(',').and_then(|(x, y)| {
+                Some(Point {
+                    x: x.parse().ok()?,
+                    y: y.parse().ok()?,
+                })
+            });
+
+            point.ok_or_else(|| SQLiteDecodeError::new("TEXT point", value))
+        }
+    }
+
+    #[test]
+    fn to_sql() {
+        assert_eq!(
+            custom_type("location", GeoPoint).to_sql().unwrap(),
+            r#""location" POINT"#
+        );
+        assert_eq!(
+            custom_type("location", GeoPoint)
+                .primary()
+                .not_null()
+                .default(Point { x: 1.5, y: -2.0 })
+                .to_sql()
+                .unwrap(),
+            r#""location" POINT PRIMARY KEY NOT NULL DEFAULT '1.5,-2'"#
+        );
+        assert_eq!(
+            custom_type("location", GeoPoint)
+                .default(Point {
+                    x: f64::INFINITY,
+                    y: 0.0
+                })
+                .to_sql(),
+            Err(SQLiteDefinitionError::Encode {
+                column: "location",
+                source: SQLiteEncodeError::new("TEXT point", "coordinates must be finite")
+            })
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        let point = Point { x: 59.9, y: 10.7 };
+        let value = Custom::<GeoPoint>::to_sql_value(point.clone()).unwrap();
+
+        assert_eq!(value, SQLiteValue::Text("59.9,10.7".to_string()));
+        assert_eq!(Custom::<GeoPoint>::from_sql_value(value), Ok(point));
+        assert_eq!(
+            Custom::<GeoPoint>::from_sql_value(SQLiteValue::Integer(1)),
+            Err(SQLiteDecodeError::new(
+                "TEXT point",
+                SQLiteValue::Integer(1)
+            ))
+        );
+        assert_eq!(
+            Custom::<GeoPoint>::to_sql_value(Point {
+                x: f64::NAN,
+                y: 0.0
+            }),
+            Err(SQLiteEncodeError::new(
+                "TEXT point",
+                "coordinates must be finite"
+            ))
+        );
+    }
+
+    #[test]
+    fn custom_types() {
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Uuid([u8; 16]);
+
+        #[derive(Debug, Default, Clone)]
+        pub struct UuidType;
+
+        impl SQLiteCustomType for UuidType {
+            type DataType = Uuid;
+
+            const SQL_TYPE: &'static str = "UUID";
+
+            fn to_driver(value: Uuid) -> Result<SQLiteValue, SQLiteEncodeError> {
+                Ok(SQLiteValue::Blob(value.0.to_vec()))
+            }
+
+            fn from_driver(value: SQLiteValue) -> Result<Uuid, SQLiteDecodeError> {
+                match value {
+                    SQLiteValue::Blob(bytes) => bytes
+                        .as_slice()
+                        .try_into()
+                        .map(Uuid)
+                        .map_err(|_| SQLiteDecodeError::new("UUID", SQLiteValue::Blob(bytes))),
+                    value => Err(SQLiteDecodeError::new("UUID", value)),
+                }
+            }
+        }
+
+        crate::sqlite_table!("devices", {
+            id: custom_type("id", UuidType).primary().not_null(),
+            parent_id: custom_type("parent_id", UuidType),
+            name: text("name", SQLiteTextMode::String).not_null(),
+        });
+
+        let devices = Devices::new();
+
+        assert_eq!(
+            devices.clone().to_sql().unwrap(),
+            concat!(
+                r#"CREATE TABLE "devices" ("id" UUID PRIMARY KEY NOT NULL, "#,
+                r#""parent_id" UUID, "name" TEXT NOT NULL)"#
+            )
+        );
+
+        let id = Uuid([7; 16]);
+        let statement = insert(&devices)
+            .values(InsertDevices {
+                id: Some(id.clone()),
+                parent_id: None,
+                name: Some("sensor".to_string()),
+            })
+            .unwrap();
+
+        assert_eq!(
+            statement.params(),
+            [
+                SQLiteValue::Blob(vec![7; 16]),
+                SQLiteValue::Text("sensor".to_string())
+            ]
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&devices.to_sql().unwrap(), ()).unwrap();
+        conn.execute(
+            &statement.clone().to_sql(),
+            rusqlite::params_from_iter(statement.params()),
+        )
+        .unwrap();
+
+        let stored: Vec<u8> = conn
+            .query_row(r#"SELECT "id" FROM "devices""#, (), |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(UuidType::from_driver(SQLiteValue::Blob(stored)), Ok(id));
+    }
+}
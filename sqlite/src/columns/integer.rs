@@ -1,18 +1,22 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, time::SystemTime};
 
 use common::{
     builders::column::ColumnBaseBuilder,
-    traits::{ColumnBuilder, Comparable, DefaultFn, DefaultValue, NotNull, PrimaryKey, Unique},
-    ToSQL,
+    traits::{
+        Comparable, DefaultFn, DefaultValue, ForeignKey, NotNull, PrimaryKey, Unique, UpdateFn,
+    },
 };
 
 use crate::{
     common::Integer,
-    traits::column::{Autoincrement, SQLAutoIncrement},
+    traits::column::{
+        Autoincrement, Generated, SQLAutoIncrement, SQLiteAutoincrementKey, SQLiteDataMode,
+    },
 };
 
 use super::{
-    DefaultFnNotSet, DefaultNotSet, IsPrimary, NotPrimary, NotUnique, Nullable, SQLiteColumn,
+    DefaultFnNotSet, DefaultNotSet, GeneratedNotSet, NotPrimary, NotUnique, Nullable,
+    ReferenceNotSet, SQLiteColumn, UpdateFnNotSet,
 };
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -28,27 +32,226 @@ impl Autoincrement for NotAutoIncremented {
     const AUTOINCREMENT: bool = false;
 }
 
-#[derive(Debug, Default, Clone, Copy)]
-pub enum SQLiteIntegerMode {
-    #[default]
-    Number,
-    Timestamp,
-    TimestampMS,
-    Boolean,
+/// Rust type of an integer column and how it is stored, selected by the mode the column is
+/// created with.
+pub trait IntegerMode: SQLiteDataMode<Self::DataType> + Copy {
+    type DataType: Clone;
+}
+
+/// Modes of integer columns. Each is a type, so the mode picks the column's Rust type, e.g.
+/// `integer("active", SQLiteIntegerMode::Boolean)` holds a `bool`.
+#[allow(non_snake_case)]
+pub mod SQLiteIntegerMode {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use crate::{
+        common::{Integer, SQLiteDecodeError, SQLiteEncodeError, SQLiteValue},
+        traits::column::SQLiteDataMode,
+    };
+
+    use super::IntegerMode;
+
+    /// Plain `i64`.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Number;
+
+    /// `SystemTime` stored as whole seconds since the Unix epoch, rounded down.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Timestamp;
+
+    /// `SystemTime` stored as whole milliseconds since the Unix epoch, rounded down.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct TimestampMS;
+
+    /// `bool` stored as `1` or `0`.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Boolean;
+
+    impl IntegerMode for Number {
+        type DataType = Integer;
+    }
+
+    impl SQLiteDataMode<Integer> for Number {
+        fn to_sql_value(value: Integer) -> Result<SQLiteValue, SQLiteEncodeError> {
+            Ok(SQLiteValue::Integer(value))
+        }
+
+        fn from_sql_value(value: SQLiteValue) -> Result<Integer, SQLiteDecodeError> {
+            match value {
+                SQLiteValue::Integer(value) => Ok(value),
+                value => Err(SQLiteDecodeError::new("INTEGER", value)),
+            }
+        }
+    }
+
+    impl IntegerMode for Boolean {
+        type DataType = bool;
+    }
+
+    impl SQLiteDataMode<bool> for Boolean {
+        fn to_sql_value(value: bool) -> Result<SQLiteValue, SQLiteEncodeError> {
+            Ok(SQLiteValue::Integer(value.into()))
+        }
+
+        fn from_sql_value(value: SQLiteValue) -> Result<bool, SQLiteDecodeError> {
+            match value {
+                SQLiteValue::Integer(0) => Ok(false),
+                SQLiteValue::Integer(1) => Ok(true),
+                value => Err(SQLiteDecodeError::new("INTEGER 0 or 1", value)),
+            }
+        }
+    }
+
+    /// Whole units, `per_second` to a second, between the Unix epoch and `time`, failing when
+    /// they overflow an `Integer`.
+    fn since_epoch(
+        time: SystemTime,
+        per_second: u64,
+        expected: &'static str,
+    ) -> Result<Integer, SQLiteEncodeError> {
+        let unit_nanos = u128::from(1_000_000_000 / per_second);
+
+        let units = match time.duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => Integer::try_from(elapsed.as_nanos() / unit_nanos).ok(),
+            // Rounds towards the past, so a partial unit before the epoch counts as a whole one.
+            Err(error) => i128::try_from(error.duration().as_nanos().div_ceil(unit_nanos))
+                .ok()
+                .and_then(|units| Integer::try_from(-units).ok()),
+        };
+
+        units.ok_or_else(|| SQLiteEncodeError::new(expected, format!("{time:?} is out of range")))
+    }
+
+    fn from_epoch(units: Integer, per_second: u64) -> Option<SystemTime> {
+        let elapsed = Duration::from_secs(units.unsigned_abs() / per_second)
+            + Duration::from_nanos(
+                units.unsigned_abs() % per_second * (1_000_000_000 / per_second),
+            );
+
+        if units < 0 {
+            UNIX_EPOCH.checked_sub(elapsed)
+        } else {
+            UNIX_EPOCH.checked_add(elapsed)
+        }
+    }
+
+    impl IntegerMode for Timestamp {
+        type DataType = SystemTime;
+    }
+
+    impl SQLiteDataMode<SystemTime> for Timestamp {
+        fn to_sql_value(value: SystemTime) -> Result<SQLiteValue, SQLiteEncodeError> {
+            since_epoch(value, 1, "INTEGER seconds").map(SQLiteValue::Integer)
+        }
+
+        fn from_sql_value(value: SQLiteValue) -> Result<SystemTime, SQLiteDecodeError> {
+            match value {
+                SQLiteValue::Integer(seconds) => from_epoch(seconds, 1)
+                    .ok_or_else(|| SQLiteDecodeError::new("a representable timestamp", value)),
+                value => Err(SQLiteDecodeError::new("INTEGER seconds", value)),
+            }
+        }
+    }
+
+    impl IntegerMode for TimestampMS {
+        type DataType = SystemTime;
+    }
+
+    impl SQLiteDataMode<SystemTime> for TimestampMS {
+        fn to_sql_value(value: SystemTime) -> Result<SQLiteValue, SQLiteEncodeError> {
+            since_epoch(value, 1_000, "INTEGER milliseconds").map(SQLiteValue::Integer)
+        }
+
+        fn from_sql_value(value: SQLiteValue) -> Result<SystemTime, SQLiteDecodeError> {
+            match value {
+                SQLiteValue::Integer(millis) => from_epoch(millis, 1_000)
+                    .ok_or_else(|| SQLiteDecodeError::new("a representable timestamp", value)),
+                value => Err(SQLiteDecodeError::new("INTEGER milliseconds", value)),
+            }
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    pub use super::chrono_modes::{DateTime, DateTimeMS};
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_modes {
+    use chrono::{TimeZone, Utc};
+
+    use crate::{
+        common::{SQLiteDecodeError, SQLiteEncodeError, SQLiteValue},
+        traits::column::SQLiteDataMode,
+    };
+
+    use super::IntegerMode;
+
+    /// `chrono::DateTime<Utc>` stored as whole seconds since the Unix epoch, rounded down.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct DateTime;
+
+    /// `chrono::DateTime<Utc>` stored as whole milliseconds since the Unix epoch, rounded down.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct DateTimeMS;
+
+    impl IntegerMode for DateTime {
+        type DataType = chrono::DateTime<Utc>;
+    }
+
+    impl SQLiteDataMode<chrono::DateTime<Utc>> for DateTime {
+        fn to_sql_value(value: chrono::DateTime<Utc>) -> Result<SQLiteValue, SQLiteEncodeError> {
+            Ok(SQLiteValue::Integer(value.timestamp()))
+        }
+
+        fn from_sql_value(value: SQLiteValue) -> Result<chrono::DateTime<Utc>, SQLiteDecodeError> {
+            match value {
+                SQLiteValue::Integer(seconds) => Utc
+                    .timestamp_opt(seconds, 0)
+                    .single()
+                    .ok_or_else(|| SQLiteDecodeError::new("a representable timestamp", value)),
+                value => Err(SQLiteDecodeError::new("INTEGER seconds", value)),
+            }
+        }
+    }
+
+    impl IntegerMode for DateTimeMS {
+        type DataType = chrono::DateTime<Utc>;
+    }
+
+    impl SQLiteDataMode<chrono::DateTime<Utc>> for DateTimeMS {
+        fn to_sql_value(value: chrono::DateTime<Utc>) -> Result<SQLiteValue, SQLiteEncodeError> {
+            Ok(SQLiteValue::Integer(value.timestamp_millis()))
+        }
+
+        fn from_sql_value(value: SQLiteValue) -> Result<chrono::DateTime<Utc>, SQLiteDecodeError> {
+            match value {
+                SQLiteValue::Integer(millis) => Utc
+                    .timestamp_millis_opt(millis)
+                    .single()
+                    .ok_or_else(|| SQLiteDecodeError::new("a representable timestamp", value)),
+                value => Err(SQLiteDecodeError::new("INTEGER milliseconds", value)),
+            }
+        }
+    }
 }
 
-type SQLiteIntegerColumn<
+pub type SQLiteIntegerColumn<
+    TMode = SQLiteIntegerMode::Number,
     TPrimary = NotPrimary,
     TNotNull = Nullable,
     TUnique = NotUnique,
     TAutoincrement = NotAutoIncremented,
     TDefault = DefaultNotSet,
     TDefaultFn = DefaultFnNotSet,
-    TFunc = fn() -> Result<Integer, std::fmt::Error>,
+    TFunc = fn() -> Result<<TMode as IntegerMode>::DataType, std::fmt::Error>,
+    TUpdateFn = UpdateFnNotSet,
+    TUpdateFunc = fn() -> Result<<TMode as IntegerMode>::DataType, std::fmt::Error>,
+    TReference = ReferenceNotSet,
+    TGenerated = GeneratedNotSet,
 > = SQLiteColumn<
-    i64,
+    <TMode as IntegerMode>::DataType,
     Integer,
-    SQLiteIntegerMode,
+    TMode,
     TPrimary,
     TNotNull,
     TUnique,
@@ -56,66 +259,63 @@ type SQLiteIntegerColumn<
     TDefault,
     TDefaultFn,
     TFunc,
+    TUpdateFn,
+    TUpdateFunc,
+    TReference,
+    TGenerated,
 >;
 
-type SQLiteIntegerColumnBuilderAutoIncrementNotSet<N, U, D, F, Fun> =
-    SQLiteIntegerColumn<IsPrimary, N, U, NotAutoIncremented, D, F, Fun>;
+type SQLiteIntegerColumnBuilderAutoIncrementNotSet<M, P, N, U, D, F, Fun, UF, UFun, R, G> =
+    SQLiteIntegerColumn<M, P, N, U, NotAutoIncremented, D, F, Fun, UF, UFun, R, G>;
 
-type SQLiteIntegerColumnBuilderAutoIncrementSet<N, U, D, F, Fun> =
-    SQLiteIntegerColumn<IsPrimary, N, U, IsAutoIncremented, D, F, Fun>;
+type SQLiteIntegerColumnBuilderAutoIncrementSet<M, P, N, U, D, F, Fun, UF, UFun, R, G> =
+    SQLiteIntegerColumn<M, P, N, U, IsAutoIncremented, D, F, Fun, UF, UFun, R, G>;
 
+// Implemented for every primary key state so calling `autoincrement` on a column without one
+// reports `SQLiteAutoincrementKey` instead of a missing method
 impl<
+        M: IntegerMode,
+        P: PrimaryKey,
         N: NotNull,
         U: Unique,
         D: DefaultValue,
         F: DefaultFn,
-        Fun: Fn() -> Result<Integer, std::fmt::Error> + Clone,
-    > SQLAutoIncrement for SQLiteIntegerColumnBuilderAutoIncrementNotSet<N, U, D, F, Fun>
+        Fun: Fn() -> Result<M::DataType, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<M::DataType, std::fmt::Error> + Clone,
+        R: ForeignKey,
+        G: Generated,
+    > SQLAutoIncrement
+    for SQLiteIntegerColumnBuilderAutoIncrementNotSet<M, P, N, U, D, F, Fun, UF, UFun, R, G>
 {
-    type Value = SQLiteIntegerColumnBuilderAutoIncrementSet<N, U, D, F, Fun>;
+    type Primary = P;
+    type Value = SQLiteIntegerColumnBuilderAutoIncrementSet<M, P, N, U, D, F, Fun, UF, UFun, R, G>;
 
-    fn autoincrement(self) -> Self::Value {
+    fn autoincrement(self) -> Self::Value
+    where
+        P: SQLiteAutoincrementKey<IsAutoIncremented>,
+    {
         SQLiteIntegerColumnBuilderAutoIncrementSet {
             base: self.base,
             default: self.default,
+            default_sql: self.default_sql,
             default_fn: self.default_fn,
+            on_update_sql: self.on_update_sql,
+            on_update_fn: self.on_update_fn,
             unique_name: self.unique_name,
+            primary_conflict: self.primary_conflict,
+            not_null_conflict: self.not_null_conflict,
+            unique_conflict: self.unique_conflict,
+            references: self.references,
+            checks: self.checks,
+            generated: self.generated,
+            collation: self.collation,
             _marker: PhantomData,
         }
     }
 }
 
-impl<
-        P: PrimaryKey,
-        N: NotNull,
-        U: Unique,
-        A: Autoincrement,
-        D: DefaultValue,
-        F: DefaultFn,
-        Fun: Fn() -> Result<Integer, std::fmt::Error> + Clone,
-    > ToSQL for SQLiteIntegerColumn<P, N, U, A, D, F, Fun>
-{
-    fn to_sql(self) -> String {
-        let name = format!(r#""{}""#, self.base.name);
-        let mut sql = vec![name.as_str(), "INTEGER"];
-
-        if P::IS_PRIMARY && !U::IS_UNIQUE {
-            sql.push("PRIMARY KEY");
-        }
-
-        if A::AUTOINCREMENT {
-            sql.push("AUTOINCREMENT");
-        }
-
-        if N::IS_NOT_NULL {
-            sql.push("NOT NULL");
-        }
-
-        sql.join(" ").to_string()
-    }
-}
-
-pub(crate) fn integer(name: &'static str, mode: SQLiteIntegerMode) -> SQLiteIntegerColumn {
+pub fn integer<M: IntegerMode>(name: &'static str, mode: M) -> SQLiteIntegerColumn<M> {
     SQLiteIntegerColumn {
         base: ColumnBaseBuilder {
             name,
@@ -126,40 +326,214 @@ pub(crate) fn integer(name: &'static str, mode: SQLiteIntegerMode) -> SQLiteInte
     }
 }
 
-impl<P: PrimaryKey, N: NotNull, U: Unique, A: Autoincrement, D: DefaultValue, F: DefaultFn>
-    Comparable<i64> for SQLiteIntegerColumn<P, N, U, A, D, F>
-{
-}
-impl<P: PrimaryKey, N: NotNull, U: Unique, A: Autoincrement, D: DefaultValue, F: DefaultFn>
-    Comparable<&i64> for SQLiteIntegerColumn<P, N, U, A, D, F>
-{
+macro_rules! impl_integer_comparable {
+    ($($mode:ident => $data_type:ty),* $(,)?) => {
+        $(
+            impl<P: PrimaryKey, N: NotNull, U: Unique, A: Autoincrement, D: DefaultValue, F: DefaultFn>
+                Comparable<$data_type> for SQLiteIntegerColumn<SQLiteIntegerMode::$mode, P, N, U, A, D, F>
+            {
+            }
+            impl<P: PrimaryKey, N: NotNull, U: Unique, A: Autoincrement, D: DefaultValue, F: DefaultFn>
+                Comparable<&$data_type> for SQLiteIntegerColumn<SQLiteIntegerMode::$mode, P, N, U, A, D, F>
+            {
+            }
+        )*
+    };
 }
 
-impl<P: PrimaryKey, N: NotNull, U: Unique, A: Autoincrement, D: DefaultValue, F: DefaultFn>
-    Comparable<Self> for SQLiteIntegerColumn<P, N, U, A, D, F>
+impl_integer_comparable!(
+    Number => Integer,
+    Boolean => bool,
+    Timestamp => SystemTime,
+    TimestampMS => SystemTime,
+);
+
+#[cfg(feature = "chrono")]
+impl_integer_comparable!(
+    DateTime => chrono::DateTime<chrono::Utc>,
+    DateTimeMS => chrono::DateTime<chrono::Utc>,
+);
+
+impl<
+        M: IntegerMode,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        A: Autoincrement,
+        D: DefaultValue,
+        F: DefaultFn,
+    > Comparable<Self> for SQLiteIntegerColumn<M, P, N, U, A, D, F>
 {
 }
 
-impl<P: PrimaryKey, N: NotNull, U: Unique, A: Autoincrement, D: DefaultValue, F: DefaultFn>
-    Comparable<Self> for &SQLiteIntegerColumn<P, N, U, A, D, F>
+impl<
+        M: IntegerMode,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        A: Autoincrement,
+        D: DefaultValue,
+        F: DefaultFn,
+    > Comparable<Self> for &SQLiteIntegerColumn<M, P, N, U, A, D, F>
 {
 }
 
 #[cfg(test)]
 mod test {
 
-    use common::traits::{SQLDefault, SQLPrimary};
+    use common::traits::{SQLDefault, SQLNotNull, SQLPrimary};
+
+    use std::time::{Duration, UNIX_EPOCH};
 
-    use crate::traits::column::SQLAutoIncrement;
+    use crate::{
+        common::{SQLiteConflict, SQLiteEncodeError, SQLiteValue},
+        traits::{
+            column::{
+                SQLAutoIncrement, SQLNotNullOnConflict, SQLPrimaryOnConflict, SQLiteDataMode,
+            },
+            table::SQLiteDefinition,
+        },
+    };
 
     use super::{integer, SQLiteIntegerMode};
 
     #[test]
     fn builder() {
-        let num = 42;
         let int = integer("id", SQLiteIntegerMode::Number)
             .default(42)
             .primary()
             .autoincrement();
+
+        assert_eq!(int.default, Some(42));
+    }
+
+    #[test]
+    fn to_sql() {
+        assert_eq!(
+            integer("id", SQLiteIntegerMode::Number)
+                .primary()
+                .autoincrement()
+                .to_sql()
+                .unwrap(),
+            r#""id" INTEGER PRIMARY KEY AUTOINCREMENT"#
+        );
+        assert_eq!(
+            integer("age", SQLiteIntegerMode::Number)
+                .not_null()
+                .to_sql()
+                .unwrap(),
+            r#""age" INTEGER NOT NULL"#
+        );
+        assert_eq!(
+            integer("age", SQLiteIntegerMode::Number)
+                .not_null()
+                .default(-1)
+                .to_sql()
+                .unwrap(),
+            r#""age" INTEGER NOT NULL DEFAULT -1"#
+        );
+        assert_eq!(
+            integer("id", SQLiteIntegerMode::Number)
+                .primary()
+                .primary_on_conflict(SQLiteConflict::Rollback)
+                .autoincrement()
+                .not_null()
+                .not_null_on_conflict(SQLiteConflict::Fail)
+                .to_sql()
+                .unwrap(),
+            r#""id" INTEGER PRIMARY KEY ON CONFLICT ROLLBACK AUTOINCREMENT NOT NULL ON CONFLICT FAIL"#
+        );
+    }
+
+    #[test]
+    fn modes() {
+        use SQLiteIntegerMode::{Boolean, Timestamp, TimestampMS};
+
+        assert_eq!(Boolean::to_sql_value(true), Ok(SQLiteValue::Integer(1)));
+        assert_eq!(Boolean::from_sql_value(SQLiteValue::Integer(0)), Ok(false));
+        assert!(Boolean::from_sql_value(SQLiteValue::Integer(2)).is_err());
+
+        let time = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        assert_eq!(
+            Timestamp::to_sql_value(time),
+            Ok(SQLiteValue::Integer(1_700_000_000))
+        );
+        assert_eq!(
+            TimestampMS::to_sql_value(time),
+            Ok(SQLiteValue::Integer(1_700_000_000_123))
+        );
+        assert_eq!(
+            TimestampMS::from_sql_value(SQLiteValue::Integer(1_700_000_000_123)),
+            Ok(time)
+        );
+
+        let before_epoch = UNIX_EPOCH - Duration::from_millis(1_500);
+        assert_eq!(
+            Timestamp::to_sql_value(before_epoch),
+            Ok(SQLiteValue::Integer(-2))
+        );
+        assert_eq!(
+            TimestampMS::from_sql_value(SQLiteValue::Integer(-1_500)),
+            Ok(before_epoch)
+        );
+        assert!(Timestamp::from_sql_value(SQLiteValue::Text("now".into())).is_err());
+
+        // Milliseconds since the epoch overflow an `Integer` long before seconds do
+        let far_future = UNIX_EPOCH + Duration::from_secs(1 << 62);
+        assert_eq!(
+            Timestamp::to_sql_value(far_future),
+            Ok(SQLiteValue::Integer(1 << 62))
+        );
+        assert_eq!(
+            TimestampMS::to_sql_value(far_future),
+            Err(SQLiteEncodeError::new(
+                "INTEGER milliseconds",
+                format!("{far_future:?} is out of range")
+            ))
+        );
+
+        assert_eq!(
+            integer("active", Boolean)
+                .not_null()
+                .default(true)
+                .to_sql()
+                .unwrap(),
+            r#""active" INTEGER NOT NULL DEFAULT 1"#
+        );
+        assert_eq!(
+            integer("seen_at", TimestampMS)
+                .default(time)
+                .to_sql()
+                .unwrap(),
+            r#""seen_at" INTEGER DEFAULT 1700000000123"#
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_modes() {
+        use chrono::{TimeZone, Utc};
+        use common::{expressions::conditions::gt, traits::ColumnBuilder};
+        use SQLiteIntegerMode::{DateTime, DateTimeMS};
+
+        use crate::{common::SQLiteDialect, expressions::encoded};
+
+        let time = Utc.timestamp_millis_opt(1_700_000_000_123).unwrap();
+        assert_eq!(
+            DateTime::to_sql_value(time),
+            Ok(SQLiteValue::Integer(1_700_000_000))
+        );
+        assert_eq!(
+            DateTimeMS::from_sql_value(DateTimeMS::to_sql_value(time).unwrap()),
+            Ok(time)
+        );
+
+        let created_at = integer("created_at", DateTime).in_table("events");
+        assert_eq!(
+            gt(&created_at, encoded(DateTime, time).unwrap())
+                .build::<SQLiteDialect>()
+                .1,
+            [SQLiteValue::Integer(1_700_000_000)]
+        );
     }
 }
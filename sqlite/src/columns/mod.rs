@@ -1,46 +1,66 @@
-use std::{
-    fmt::{self, Display},
-    marker::PhantomData,
-};
+use std::{fmt, marker::PhantomData, sync::Arc};
 
 use common::{
     builders::column::ColumnBaseBuilder,
+    expressions::{
+        conditions::{exists, not_exists, ArrayExpression},
+        Expression,
+    },
     traits::{
-        ColumnBuilder, DefaultFn, DefaultValue, NotNull, PrimaryKey, SQLDefault, SQLDefaultFn,
-        SQLNotNull, SQLPrimary, SQLUnique, Unique,
+        ColumnBuilder, DefaultFn, DefaultValue, ForeignKey, NotNull, PrimaryKey, SQLDefault,
+        SQLDefaultFn, SQLDefaultSQL, SQLNotNull, SQLOnUpdateFn, SQLOnUpdateSQL, SQLPrimary,
+        SQLUnique, Unique, UpdateFn,
     },
-    ToSQL,
+    SQLChunk, SQLFragment, SQL,
 };
 use integer::NotAutoIncremented;
 
-use crate::traits::column::Autoincrement;
+use crate::{
+    common::{
+        Collation, DefaultSQL, SQLiteConflict, SQLiteDefinitionError, SQLiteReferenceAction,
+        SQLiteValue, Text,
+    },
+    expressions::SQLiteJSONExpression,
+    query::{insert::SQLiteInsertError, update::SQLiteUpdateError},
+    table::check_table,
+    traits::column::{
+        Autoincrement, Generated, Nullability, SQLCheck, SQLCollate, SQLGenerated,
+        SQLGeneratedStored, SQLNotNullOnConflict, SQLPrimaryOnConflict, SQLReferenceActions,
+        SQLReferences, SQLUniqueOnConflict, SQLiteAutoincrementKey, SQLiteColumnData,
+        SQLiteColumnRef, SQLiteColumnType, SQLiteDataMode, SQLiteUniqueKey,
+    },
+    traits::table::SQLiteDefinition,
+};
 
-mod any;
+pub mod any;
 pub mod blob;
+pub mod custom_type;
 pub mod integer;
-mod number;
+pub mod number;
 pub mod real;
 pub mod text;
 
 #[derive(Default, Debug, Clone, Copy)]
-pub(crate) struct NotSet;
-
-#[derive(Default, Debug, Clone, Copy)]
-pub(crate) struct NoDefaultFn;
-
-#[derive(Default, Debug, Clone, Copy)]
-pub(crate) struct IsPrimary;
+pub struct IsPrimary;
 
 impl PrimaryKey for IsPrimary {
     const IS_PRIMARY: bool = true;
 }
 #[derive(Default, Debug, Clone, Copy)]
-pub(crate) struct NotPrimary;
+pub struct NotPrimary;
 
 impl PrimaryKey for NotPrimary {
     const IS_PRIMARY: bool = false;
 }
 
+impl<U: Unique> SQLiteUniqueKey<U> for NotPrimary {}
+
+impl SQLiteUniqueKey<NotUnique> for IsPrimary {}
+
+impl<A: Autoincrement> SQLiteAutoincrementKey<A> for IsPrimary {}
+
+impl SQLiteAutoincrementKey<NotAutoIncremented> for NotPrimary {}
+
 #[derive(Default, Debug, Clone, Copy)]
 pub struct NotNullable;
 
@@ -48,13 +68,29 @@ impl NotNull for NotNullable {
     const IS_NOT_NULL: bool = true;
 }
 
+impl Nullability for NotNullable {
+    type Assignable<T> = T;
+
+    fn assigned<T>(value: T) -> Option<T> {
+        Some(value)
+    }
+}
+
 #[derive(Default, Debug, Clone, Copy)]
-pub(crate) struct Nullable;
+pub struct Nullable;
 
 impl NotNull for Nullable {
     const IS_NOT_NULL: bool = false;
 }
 
+impl Nullability for Nullable {
+    type Assignable<T> = Option<T>;
+
+    fn assigned<T>(value: Option<T>) -> Option<T> {
+        value
+    }
+}
+
 #[derive(Default, Debug, Clone, Copy)]
 pub struct IsUnique;
 
@@ -82,21 +118,114 @@ impl DefaultValue for DefaultNotSet {
 }
 
 #[derive(Default, Debug, Clone, Copy)]
-pub(crate) struct DefaultFnSet;
+pub struct DefaultFnSet;
 
 impl DefaultFn for DefaultFnSet {
     const HAS_DEFAULT_FN: bool = true;
 }
 #[derive(Default, Debug, Clone, Copy)]
-pub(crate) struct DefaultFnNotSet;
+pub struct DefaultFnNotSet;
 
 impl DefaultFn for DefaultFnNotSet {
     const HAS_DEFAULT_FN: bool = false;
 }
 
+#[derive(Default, Debug, Clone, Copy)]
+pub struct UpdateFnSet;
+
+impl UpdateFn for UpdateFnSet {
+    const HAS_UPDATE_FN: bool = true;
+}
+#[derive(Default, Debug, Clone, Copy)]
+pub struct UpdateFnNotSet;
+
+impl UpdateFn for UpdateFnNotSet {
+    const HAS_UPDATE_FN: bool = false;
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ReferenceSet;
+
+impl ForeignKey for ReferenceSet {
+    const HAS_REFERENCE: bool = true;
+}
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ReferenceNotSet;
+
+impl ForeignKey for ReferenceNotSet {
+    const HAS_REFERENCE: bool = false;
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct GeneratedSet;
+
+impl Generated for GeneratedSet {
+    const IS_GENERATED: bool = true;
+    type Writable<T> = ReadOnly;
+
+    fn written<T>(value: ReadOnly) -> T {
+        match value {}
+    }
+}
+#[derive(Default, Debug, Clone, Copy)]
+pub struct GeneratedNotSet;
+
+impl Generated for GeneratedNotSet {
+    const IS_GENERATED: bool = false;
+    type Writable<T> = T;
+
+    fn written<T>(value: T) -> T {
+        value
+    }
+}
+
+/// Value type of generated columns in insert and update rows; it has no values, so those fields
+/// can only be `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadOnly {}
+
+/// The column a foreign key points at, resolved lazily so tables can reference each other.
+#[derive(Clone)]
+pub struct SQLiteReference {
+    pub(crate) column: Arc<dyn Fn() -> (&'static str, &'static str) + Send + Sync>,
+    pub(crate) on_delete: Option<SQLiteReferenceAction>,
+    pub(crate) on_update: Option<SQLiteReferenceAction>,
+    pub(crate) deferrable: bool,
+}
+
+impl fmt::Debug for SQLiteReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (table, column) = (self.column)();
+
+        f.debug_struct("SQLiteReference")
+            .field("table", &table)
+            .field("column", &column)
+            .field("on_delete", &self.on_delete)
+            .field("on_update", &self.on_update)
+            .field("deferrable", &self.deferrable)
+            .finish()
+    }
+}
+
+/// Expression computing a generated column, resolved lazily like [`SQLiteReference`].
+#[derive(Clone)]
+pub struct SQLiteGenerated {
+    pub(crate) expression: Arc<dyn Fn() -> SQL<SQLiteValue> + Send + Sync>,
+    pub(crate) stored: bool,
+}
+
+impl fmt::Debug for SQLiteGenerated {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SQLiteGenerated")
+            .field("expression", &(self.expression)())
+            .field("stored", &self.stored)
+            .finish()
+    }
+}
+
 #[derive(Debug, Clone)]
-pub(crate) struct SQLiteColumn<
-    DataType: Default + Clone,
+pub struct SQLiteColumn<
+    DataType: Clone,
     ColumnType: Default + Clone,
     DataMode: Default + Clone,
     TPrimary: PrimaryKey = NotPrimary,
@@ -109,17 +238,29 @@ pub(crate) struct SQLiteColumn<
         DataType,
         std::fmt::Error,
     >,
-    // TUpdate,
-    // TUpdateFn,
-    // TUpdateFunc,
-    // TConflict,
-    // TConflictFn,
-    // TConflictFunc,
+    TUpdateFn: UpdateFn = UpdateFnNotSet,
+    TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone = fn() -> Result<
+        DataType,
+        std::fmt::Error,
+    >,
+    TReference: ForeignKey = ReferenceNotSet,
+    TGenerated: Generated = GeneratedNotSet,
 > {
     pub(crate) base: ColumnBaseBuilder<DataType, ColumnType, DataMode>,
     pub(crate) unique_name: Option<&'static str>,
+    pub(crate) primary_conflict: Option<SQLiteConflict>,
+    pub(crate) not_null_conflict: Option<SQLiteConflict>,
+    pub(crate) unique_conflict: Option<SQLiteConflict>,
     pub(crate) default: Option<DataType>,
+    pub(crate) default_sql: Option<&'static str>,
     pub(crate) default_fn: Option<TFunc>,
+    pub(crate) on_update_sql: Option<&'static str>,
+    pub(crate) on_update_fn: Option<TUpdateFunc>,
+    pub(crate) references: Option<SQLiteReference>,
+    pub(crate) checks: Vec<(&'static str, SQL<SQLiteValue, bool>)>,
+    pub(crate) generated: Option<SQLiteGenerated>,
+    pub(crate) collation: Option<Collation>,
+    #[allow(clippy::type_complexity)]
     pub(crate) _marker: PhantomData<(
         TPrimary,
         TNotNull,
@@ -127,13 +268,14 @@ pub(crate) struct SQLiteColumn<
         TAutoincrement,
         TDefault,
         TDefaultFn,
+        TUpdateFn,
+        TReference,
+        TGenerated,
     )>,
-    // onUpdateFn: Option<Fn2>,
-    // uniqueName: Option<String>,
 }
 
 impl<
-        DataType: Default + Clone,
+        DataType: Clone,
         ColumnType: Default + Clone,
         DataMode: Default + Clone,
         P: PrimaryKey,
@@ -142,60 +284,206 @@ impl<
         A: Autoincrement,
         D: DefaultValue,
         F: DefaultFn,
-    > Default for SQLiteColumn<DataType, ColumnType, DataMode, P, N, U, A, D, F>
+        UF: UpdateFn,
+        R: ForeignKey,
+        G: Generated,
+    > Default
+    for SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        N,
+        U,
+        A,
+        D,
+        F,
+        fn() -> Result<DataType, fmt::Error>,
+        UF,
+        fn() -> Result<DataType, fmt::Error>,
+        R,
+        G,
+    >
 {
     fn default() -> Self {
         Self {
             base: ColumnBaseBuilder::default(),
             unique_name: None,
+            primary_conflict: None,
+            not_null_conflict: None,
+            unique_conflict: None,
+            references: None,
+            checks: vec![],
+            generated: None,
+            collation: None,
             default: None,
+            default_sql: None,
             default_fn: None,
+            on_update_sql: None,
+            on_update_fn: None,
             _marker: PhantomData,
         }
     }
 }
 
 // Primary FieldSet
-type SQLiteColumnBuilderPrimaryNotSet<DataType, ColumnType, DataMode, N, U, A, D, F, Fun> =
-    SQLiteColumn<DataType, ColumnType, DataMode, NotPrimary, N, U, A, D, F, Fun>;
+type SQLiteColumnBuilderPrimaryNotSet<
+    DataType,
+    ColumnType,
+    DataMode,
+    N,
+    A,
+    D,
+    F,
+    Fun,
+    UF,
+    UFun,
+    R,
+> = SQLiteColumn<
+    DataType,
+    ColumnType,
+    DataMode,
+    NotPrimary,
+    N,
+    NotUnique,
+    A,
+    D,
+    F,
+    Fun,
+    UF,
+    UFun,
+    R,
+    GeneratedNotSet,
+>;
 
-type SQLiteColumnBuilderPrimarySet<DataType, ColumnType, DataMode, N, U, A, D, F, Fun> =
-    SQLiteColumn<DataType, ColumnType, DataMode, IsPrimary, N, U, A, D, F, Fun>;
+type SQLiteColumnBuilderPrimarySet<DataType, ColumnType, DataMode, N, A, D, F, Fun, UF, UFun, R> =
+    SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        IsPrimary,
+        N,
+        NotUnique,
+        A,
+        D,
+        F,
+        Fun,
+        UF,
+        UFun,
+        R,
+        GeneratedNotSet,
+    >;
 
 impl<
-        DataType: Default + Clone,
+        DataType: Clone,
         ColumnType: Default + Clone,
         DataMode: Default + Clone,
         N: NotNull,
-        U: Unique,
         A: Autoincrement,
         D: DefaultValue,
         F: DefaultFn,
         Fun: Fn() -> Result<DataType, std::fmt::Error> + Sized + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        R: ForeignKey,
     > SQLPrimary
-    for SQLiteColumnBuilderPrimaryNotSet<DataType, ColumnType, DataMode, N, U, A, D, F, Fun>
+    for SQLiteColumnBuilderPrimaryNotSet<
+        DataType,
+        ColumnType,
+        DataMode,
+        N,
+        A,
+        D,
+        F,
+        Fun,
+        UF,
+        UFun,
+        R,
+    >
 {
-    type Value = SQLiteColumnBuilderPrimarySet<DataType, ColumnType, DataMode, N, U, A, D, F, Fun>;
+    type Value =
+        SQLiteColumnBuilderPrimarySet<DataType, ColumnType, DataMode, N, A, D, F, Fun, UF, UFun, R>;
     fn primary(self) -> Self::Value {
         SQLiteColumn {
             base: self.base,
             default: self.default,
+            default_sql: self.default_sql,
             default_fn: self.default_fn,
+            on_update_sql: self.on_update_sql,
+            on_update_fn: self.on_update_fn,
             unique_name: self.unique_name,
+            primary_conflict: self.primary_conflict,
+            not_null_conflict: self.not_null_conflict,
+            unique_conflict: self.unique_conflict,
+            references: self.references,
+            checks: self.checks,
+            generated: self.generated,
+            collation: self.collation,
             _marker: PhantomData,
         }
     }
 }
 
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        N: NotNull,
+        A: Autoincrement,
+        D: DefaultValue,
+        F: DefaultFn,
+        Fun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        R: ForeignKey,
+    > SQLPrimaryOnConflict
+    for SQLiteColumnBuilderPrimarySet<DataType, ColumnType, DataMode, N, A, D, F, Fun, UF, UFun, R>
+{
+    type Conflict = SQLiteConflict;
+
+    fn primary_on_conflict(self, conflict: Self::Conflict) -> Self {
+        SQLiteColumn {
+            primary_conflict: Some(conflict),
+            ..self
+        }
+    }
+}
+
 // Not Null FieldSet
-type SQLiteColumnBuilderNotNullNotSet<DataType, ColumnType, DataMode, P, U, A, D, F, Fun> =
-    SQLiteColumn<DataType, ColumnType, DataMode, P, Nullable, U, A, D, F, Fun>;
+type SQLiteColumnBuilderNotNullNotSet<
+    DataType,
+    ColumnType,
+    DataMode,
+    P,
+    U,
+    A,
+    D,
+    F,
+    Fun,
+    UF,
+    UFun,
+    R,
+    G,
+> = SQLiteColumn<DataType, ColumnType, DataMode, P, Nullable, U, A, D, F, Fun, UF, UFun, R, G>;
 
-type SQLiteColumnBuilderNotNullSet<DataType, ColumnType, DataMode, P, U, A, D, F, Fun> =
-    SQLiteColumn<DataType, ColumnType, DataMode, P, NotNullable, U, A, D, F, Fun>;
+type SQLiteColumnBuilderNotNullSet<
+    DataType,
+    ColumnType,
+    DataMode,
+    P,
+    U,
+    A,
+    D,
+    F,
+    Fun,
+    UF,
+    UFun,
+    R,
+    G,
+> = SQLiteColumn<DataType, ColumnType, DataMode, P, NotNullable, U, A, D, F, Fun, UF, UFun, R, G>;
 
 impl<
-        DataType: Default + Clone,
+        DataType: Clone,
         ColumnType: Default + Clone,
         DataMode: Default + Clone,
         P: PrimaryKey,
@@ -204,74 +492,286 @@ impl<
         D: DefaultValue,
         F: DefaultFn,
         Fun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        R: ForeignKey,
+        G: Generated,
     > SQLNotNull
-    for SQLiteColumnBuilderNotNullNotSet<DataType, ColumnType, DataMode, P, U, A, D, F, Fun>
+    for SQLiteColumnBuilderNotNullNotSet<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        U,
+        A,
+        D,
+        F,
+        Fun,
+        UF,
+        UFun,
+        R,
+        G,
+    >
 {
-    type Value = SQLiteColumnBuilderNotNullSet<DataType, ColumnType, DataMode, P, U, A, D, F, Fun>;
+    type Value = SQLiteColumnBuilderNotNullSet<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        U,
+        A,
+        D,
+        F,
+        Fun,
+        UF,
+        UFun,
+        R,
+        G,
+    >;
     fn not_null(self) -> Self::Value {
         SQLiteColumn {
             base: self.base,
             default: self.default,
+            default_sql: self.default_sql,
             default_fn: self.default_fn,
+            on_update_sql: self.on_update_sql,
+            on_update_fn: self.on_update_fn,
             unique_name: self.unique_name,
+            primary_conflict: self.primary_conflict,
+            not_null_conflict: self.not_null_conflict,
+            unique_conflict: self.unique_conflict,
+            references: self.references,
+            checks: self.checks,
+            generated: self.generated,
+            collation: self.collation,
             _marker: PhantomData,
         }
     }
 }
 
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        P: PrimaryKey,
+        U: Unique,
+        A: Autoincrement,
+        D: DefaultValue,
+        F: DefaultFn,
+        Fun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        R: ForeignKey,
+        G: Generated,
+    > SQLNotNullOnConflict
+    for SQLiteColumnBuilderNotNullSet<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        U,
+        A,
+        D,
+        F,
+        Fun,
+        UF,
+        UFun,
+        R,
+        G,
+    >
+{
+    type Conflict = SQLiteConflict;
+
+    fn not_null_on_conflict(self, conflict: Self::Conflict) -> Self {
+        SQLiteColumn {
+            not_null_conflict: Some(conflict),
+            ..self
+        }
+    }
+}
+
 // Default FieldSet
-type SQLiteColumnBuilderDefaultNotSet<DataType, ColumnType, DataMode, P, N, U, A> =
-    SQLiteColumn<DataType, ColumnType, DataMode, P, N, U, A, DefaultNotSet, DefaultFnNotSet>;
+type SQLiteColumnBuilderDefaultNotSet<DataType, ColumnType, DataMode, P, N, U, A, UF, UFun, R> =
+    SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        N,
+        U,
+        A,
+        DefaultNotSet,
+        DefaultFnNotSet,
+        fn() -> Result<DataType, fmt::Error>,
+        UF,
+        UFun,
+        R,
+        GeneratedNotSet,
+    >;
 
-type SQLiteColumnBuilderDefaultSet<DataType, ColumnType, DataMode, P, N, U, A> =
-    SQLiteColumn<DataType, ColumnType, DataMode, P, N, U, A, DefaultSet, DefaultFnNotSet>;
+type SQLiteColumnBuilderDefaultSet<DataType, ColumnType, DataMode, P, N, U, A, UF, UFun, R> =
+    SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        N,
+        U,
+        A,
+        DefaultSet,
+        DefaultFnNotSet,
+        fn() -> Result<DataType, fmt::Error>,
+        UF,
+        UFun,
+        R,
+        GeneratedNotSet,
+    >;
 
 impl<
-        DataType: Default + Clone,
+        DataType: Clone,
         ColumnType: Default + Clone,
-        DataMode: Default + Clone,
+        DataMode: SQLiteDataMode<DataType>,
         P: PrimaryKey,
         N: NotNull,
         U: Unique,
         A: Autoincrement,
-    > SQLDefault for SQLiteColumnBuilderDefaultNotSet<DataType, ColumnType, DataMode, P, N, U, A>
+        UF: UpdateFn,
+        UFun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        R: ForeignKey,
+    > SQLDefault
+    for SQLiteColumnBuilderDefaultNotSet<DataType, ColumnType, DataMode, P, N, U, A, UF, UFun, R>
 {
-    type Value = SQLiteColumnBuilderDefaultSet<DataType, ColumnType, DataMode, P, N, U, A>;
+    type Value =
+        SQLiteColumnBuilderDefaultSet<DataType, ColumnType, DataMode, P, N, U, A, UF, UFun, R>;
     type DataType = DataType;
 
     fn default(self, value: Self::DataType) -> Self::Value {
         SQLiteColumn {
             base: self.base,
             default: Some(value),
+            default_sql: self.default_sql,
+            default_fn: self.default_fn,
+            on_update_sql: self.on_update_sql,
+            on_update_fn: self.on_update_fn,
+            unique_name: self.unique_name,
+            primary_conflict: self.primary_conflict,
+            not_null_conflict: self.not_null_conflict,
+            unique_conflict: self.unique_conflict,
+            references: self.references,
+            checks: self.checks,
+            generated: self.generated,
+            collation: self.collation,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        A: Autoincrement,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        R: ForeignKey,
+    > SQLDefaultSQL
+    for SQLiteColumnBuilderDefaultNotSet<DataType, ColumnType, DataMode, P, N, U, A, UF, UFun, R>
+{
+    type Expression = DefaultSQL<ColumnType>;
+    type Value =
+        SQLiteColumnBuilderDefaultSet<DataType, ColumnType, DataMode, P, N, U, A, UF, UFun, R>;
+
+    fn default_sql(self, sql: Self::Expression) -> Self::Value {
+        SQLiteColumn {
+            base: self.base,
+            default: self.default,
+            default_sql: Some(sql.sql),
             default_fn: self.default_fn,
+            on_update_sql: self.on_update_sql,
+            on_update_fn: self.on_update_fn,
             unique_name: self.unique_name,
+            primary_conflict: self.primary_conflict,
+            not_null_conflict: self.not_null_conflict,
+            unique_conflict: self.unique_conflict,
+            references: self.references,
+            checks: self.checks,
+            generated: self.generated,
+            collation: self.collation,
             _marker: PhantomData,
         }
     }
 }
 
 // DefaultFn FieldSet
-type SQLiteColumnBuilderDefaultFnNotSet<DataType, ColumnType, DataMode, P, N, U, A> =
-    SQLiteColumn<DataType, ColumnType, DataMode, P, N, U, A, DefaultNotSet, DefaultFnNotSet>;
+type SQLiteColumnBuilderDefaultFnNotSet<DataType, ColumnType, DataMode, P, N, U, A, UF, UFun, R> =
+    SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        N,
+        U,
+        A,
+        DefaultNotSet,
+        DefaultFnNotSet,
+        fn() -> Result<DataType, fmt::Error>,
+        UF,
+        UFun,
+        R,
+        GeneratedNotSet,
+    >;
 
-type SQLiteColumnBuilderDefaultFnSet<DataType, ColumnType, DataMode, P, N, U, A, F> =
-    SQLiteColumn<DataType, ColumnType, DataMode, P, N, U, A, DefaultNotSet, DefaultFnSet, F>;
+type SQLiteColumnBuilderDefaultFnSet<DataType, ColumnType, DataMode, P, N, U, A, F, UF, UFun, R> =
+    SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        N,
+        U,
+        A,
+        DefaultNotSet,
+        DefaultFnSet,
+        F,
+        UF,
+        UFun,
+        R,
+        GeneratedNotSet,
+    >;
 
 impl<
-        DataType: Default + Clone,
+        DataType: Clone,
         ColumnType: Default + Clone,
         DataMode: Default + Clone,
         P: PrimaryKey,
         N: NotNull,
         U: Unique,
         A: Autoincrement,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        R: ForeignKey,
     > SQLDefaultFn
-    for SQLiteColumnBuilderDefaultFnNotSet<DataType, ColumnType, DataMode, P, N, U, A>
+    for SQLiteColumnBuilderDefaultFnNotSet<DataType, ColumnType, DataMode, P, N, U, A, UF, UFun, R>
 {
     type DataType = DataType;
     type Error = fmt::Error;
     type Value<Func: Fn() -> Result<DataType, fmt::Error> + Clone> =
-        SQLiteColumnBuilderDefaultFnSet<DataType, ColumnType, DataMode, P, N, U, A, Func>;
+        SQLiteColumnBuilderDefaultFnSet<
+            DataType,
+            ColumnType,
+            DataMode,
+            P,
+            N,
+            U,
+            A,
+            Func,
+            UF,
+            UFun,
+            R,
+        >;
 
     fn default_fn<Func>(self, value: Func) -> Self::Value<Func>
     where
@@ -280,107 +780,1699 @@ impl<
         SQLiteColumn {
             base: self.base,
             default: self.default,
+            default_sql: self.default_sql,
             default_fn: Some(value),
+            on_update_sql: self.on_update_sql,
+            on_update_fn: self.on_update_fn,
             unique_name: self.unique_name,
+            primary_conflict: self.primary_conflict,
+            not_null_conflict: self.not_null_conflict,
+            unique_conflict: self.unique_conflict,
+            references: self.references,
+            checks: self.checks,
+            generated: self.generated,
+            collation: self.collation,
             _marker: PhantomData,
         }
     }
 }
 
-// Unique FieldSet
-type SQLiteColumnBuilderUniqueNotSet<DataType, ColumnType, DataMode, N, A, D, F, Fun> =
-    SQLiteColumn<DataType, ColumnType, DataMode, NotPrimary, N, NotUnique, A, D, F, Fun>;
+// UpdateFn FieldSet
+type SQLiteColumnBuilderUpdateFnNotSet<DataType, ColumnType, DataMode, P, N, U, A, D, F, Fun, R> =
+    SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        N,
+        U,
+        A,
+        D,
+        F,
+        Fun,
+        UpdateFnNotSet,
+        fn() -> Result<DataType, fmt::Error>,
+        R,
+        GeneratedNotSet,
+    >;
 
-type SQLiteColumnBuilderUniqueSet<DataType, ColumnType, DataMode, N, A, D, F, Fun> =
-    SQLiteColumn<DataType, ColumnType, DataMode, NotPrimary, N, IsUnique, A, D, F, Fun>;
+type SQLiteColumnBuilderUpdateFnSet<
+    DataType,
+    ColumnType,
+    DataMode,
+    P,
+    N,
+    U,
+    A,
+    D,
+    F,
+    Fun,
+    UFun,
+    R,
+> = SQLiteColumn<
+    DataType,
+    ColumnType,
+    DataMode,
+    P,
+    N,
+    U,
+    A,
+    D,
+    F,
+    Fun,
+    UpdateFnSet,
+    UFun,
+    R,
+    GeneratedNotSet,
+>;
 
 impl<
-        DataType: Default + Clone,
+        DataType: Clone,
         ColumnType: Default + Clone,
         DataMode: Default + Clone,
+        P: PrimaryKey,
         N: NotNull,
+        U: Unique,
         A: Autoincrement,
         D: DefaultValue,
         F: DefaultFn,
         Fun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
-    > SQLUnique
-    for SQLiteColumnBuilderUniqueNotSet<DataType, ColumnType, DataMode, N, A, D, F, Fun>
+        R: ForeignKey,
+    > SQLOnUpdateFn
+    for SQLiteColumnBuilderUpdateFnNotSet<DataType, ColumnType, DataMode, P, N, U, A, D, F, Fun, R>
 {
-    type Value = SQLiteColumnBuilderUniqueSet<DataType, ColumnType, DataMode, N, A, D, F, Fun>;
+    type DataType = DataType;
+    type Error = fmt::Error;
+    type Value<UFun: Fn() -> Result<DataType, fmt::Error> + Clone> = SQLiteColumnBuilderUpdateFnSet<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        N,
+        U,
+        A,
+        D,
+        F,
+        Fun,
+        UFun,
+        R,
+    >;
 
-    fn unique(self, value: &'static str) -> Self::Value {
+    fn on_update_fn<UFun>(self, value: UFun) -> Self::Value<UFun>
+    where
+        UFun: Fn() -> Result<DataType, fmt::Error> + Clone,
+    {
         SQLiteColumn {
             base: self.base,
             default: self.default,
+            default_sql: self.default_sql,
             default_fn: self.default_fn,
-            unique_name: Some(value),
+            on_update_sql: self.on_update_sql,
+            on_update_fn: Some(value),
+            unique_name: self.unique_name,
+            primary_conflict: self.primary_conflict,
+            not_null_conflict: self.not_null_conflict,
+            unique_conflict: self.unique_conflict,
+            references: self.references,
+            checks: self.checks,
+            generated: self.generated,
+            collation: self.collation,
             _marker: PhantomData,
         }
     }
 }
 
 impl<
-        DataType: Default + Clone,
+        DataType: Clone,
         ColumnType: Default + Clone,
         DataMode: Default + Clone,
-        TPrimary: PrimaryKey,
-        TNotNull: NotNull,
-        TUnique: Unique,
-        TAutoincrement: Autoincrement,
-        TDefault: DefaultValue,
-        TDefaultFn: DefaultFn,
-        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
-    > Display
-    for SQLiteColumn<
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        A: Autoincrement,
+        D: DefaultValue,
+        F: DefaultFn,
+        Fun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        R: ForeignKey,
+    > SQLOnUpdateSQL
+    for SQLiteColumnBuilderUpdateFnNotSet<DataType, ColumnType, DataMode, P, N, U, A, D, F, Fun, R>
+{
+    type Expression = DefaultSQL<ColumnType>;
+    type Value = SQLiteColumnBuilderUpdateFnSet<
         DataType,
         ColumnType,
         DataMode,
-        TPrimary,
-        TNotNull,
-        TUnique,
-        TAutoincrement,
-        TDefault,
-        TDefaultFn,
-        TFunc,
+        P,
+        N,
+        U,
+        A,
+        D,
+        F,
+        Fun,
+        fn() -> Result<DataType, fmt::Error>,
+        R,
+    >;
+
+    fn on_update_sql(self, sql: Self::Expression) -> Self::Value {
+        SQLiteColumn {
+            base: self.base,
+            default: self.default,
+            default_sql: self.default_sql,
+            default_fn: self.default_fn,
+            on_update_sql: Some(sql.sql),
+            on_update_fn: self.on_update_fn,
+            unique_name: self.unique_name,
+            primary_conflict: self.primary_conflict,
+            not_null_conflict: self.not_null_conflict,
+            unique_conflict: self.unique_conflict,
+            references: self.references,
+            checks: self.checks,
+            generated: self.generated,
+            collation: self.collation,
+            _marker: PhantomData,
+        }
+    }
+}
+
+// Unique FieldSet
+type SQLiteColumnBuilderUniqueNotSet<
+    DataType,
+    ColumnType,
+    DataMode,
+    N,
+    A,
+    D,
+    F,
+    Fun,
+    UF,
+    UFun,
+    R,
+    G,
+> = SQLiteColumn<
+    DataType,
+    ColumnType,
+    DataMode,
+    NotPrimary,
+    N,
+    NotUnique,
+    A,
+    D,
+    F,
+    Fun,
+    UF,
+    UFun,
+    R,
+    G,
+>;
+
+type SQLiteColumnBuilderUniqueSet<DataType, ColumnType, DataMode, N, A, D, F, Fun, UF, UFun, R, G> =
+    SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        NotPrimary,
+        N,
+        IsUnique,
+        A,
+        D,
+        F,
+        Fun,
+        UF,
+        UFun,
+        R,
+        G,
+    >;
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        N: NotNull,
+        A: Autoincrement,
+        D: DefaultValue,
+        F: DefaultFn,
+        Fun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        R: ForeignKey,
+        G: Generated,
+    > SQLUnique
+    for SQLiteColumnBuilderUniqueNotSet<
+        DataType,
+        ColumnType,
+        DataMode,
+        N,
+        A,
+        D,
+        F,
+        Fun,
+        UF,
+        UFun,
+        R,
+        G,
     >
-where
-    Self: ToSQL,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.clone().to_sql())
+    type Value = SQLiteColumnBuilderUniqueSet<
+        DataType,
+        ColumnType,
+        DataMode,
+        N,
+        A,
+        D,
+        F,
+        Fun,
+        UF,
+        UFun,
+        R,
+        G,
+    >;
+
+    fn unique(self, value: &'static str) -> Self::Value {
+        SQLiteColumn {
+            base: self.base,
+            default: self.default,
+            default_sql: self.default_sql,
+            default_fn: self.default_fn,
+            on_update_sql: self.on_update_sql,
+            on_update_fn: self.on_update_fn,
+            unique_name: Some(value),
+            primary_conflict: self.primary_conflict,
+            not_null_conflict: self.not_null_conflict,
+            unique_conflict: self.unique_conflict,
+            references: self.references,
+            checks: self.checks,
+            generated: self.generated,
+            collation: self.collation,
+            _marker: PhantomData,
+        }
     }
 }
 
 impl<
-        DataType: Default + Clone,
+        DataType: Clone,
         ColumnType: Default + Clone,
         DataMode: Default + Clone,
-        TPrimary: PrimaryKey,
-        TNotNull: NotNull,
-        TUnique: Unique,
-        TAutoincrement: Autoincrement,
-        TDefault: DefaultValue,
-        TDefaultFn: DefaultFn,
-        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
-    > ColumnBuilder
-    for SQLiteColumn<
+        N: NotNull,
+        A: Autoincrement,
+        D: DefaultValue,
+        F: DefaultFn,
+        Fun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        R: ForeignKey,
+        G: Generated,
+    > SQLUniqueOnConflict
+    for SQLiteColumnBuilderUniqueSet<
         DataType,
         ColumnType,
         DataMode,
-        TPrimary,
-        TNotNull,
-        TUnique,
-        TAutoincrement,
-        TDefault,
-        TDefaultFn,
-        TFunc,
+        N,
+        A,
+        D,
+        F,
+        Fun,
+        UF,
+        UFun,
+        R,
+        G,
     >
 {
-    fn name(&self) -> &str {
-        self.base.name
+    type Conflict = SQLiteConflict;
+
+    fn unique_on_conflict(self, conflict: Self::Conflict) -> Self {
+        SQLiteColumn {
+            unique_conflict: Some(conflict),
+            ..self
+        }
     }
+}
 
-    fn build(self) -> Self {
+// Generated FieldSet
+type SQLiteColumnBuilderGeneratedNotSet<DataType, ColumnType, DataMode, N, U, A, R> = SQLiteColumn<
+    DataType,
+    ColumnType,
+    DataMode,
+    NotPrimary,
+    N,
+    U,
+    A,
+    DefaultNotSet,
+    DefaultFnNotSet,
+    fn() -> Result<DataType, fmt::Error>,
+    UpdateFnNotSet,
+    fn() -> Result<DataType, fmt::Error>,
+    R,
+    GeneratedNotSet,
+>;
+
+type SQLiteColumnBuilderGeneratedSet<DataType, ColumnType, DataMode, N, U, A, R> = SQLiteColumn<
+    DataType,
+    ColumnType,
+    DataMode,
+    NotPrimary,
+    N,
+    U,
+    A,
+    DefaultNotSet,
+    DefaultFnNotSet,
+    fn() -> Result<DataType, fmt::Error>,
+    UpdateFnNotSet,
+    fn() -> Result<DataType, fmt::Error>,
+    R,
+    GeneratedSet,
+>;
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        N: NotNull,
+        U: Unique,
+        A: Autoincrement,
+        R: ForeignKey,
+    > SQLGenerated
+    for SQLiteColumnBuilderGeneratedNotSet<DataType, ColumnType, DataMode, N, U, A, R>
+{
+    type Expression = SQL<SQLiteValue, DataType>;
+    type Value = SQLiteColumnBuilderGeneratedSet<DataType, ColumnType, DataMode, N, U, A, R>;
+
+    fn generated_always_as<T, F>(self, expression: F) -> Self::Value
+    where
+        T: Default,
+        F: Fn(&T) -> Self::Expression + Send + Sync + 'static,
+    {
+        SQLiteColumn {
+            base: self.base,
+            default: self.default,
+            default_sql: self.default_sql,
+            default_fn: self.default_fn,
+            on_update_sql: self.on_update_sql,
+            on_update_fn: self.on_update_fn,
+            unique_name: self.unique_name,
+            primary_conflict: self.primary_conflict,
+            not_null_conflict: self.not_null_conflict,
+            unique_conflict: self.unique_conflict,
+            references: self.references,
+            checks: self.checks,
+            generated: Some(SQLiteGenerated {
+                expression: Arc::new(move || expression(&T::default()).cast()),
+                stored: false,
+            }),
+            collation: self.collation,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        N: NotNull,
+        U: Unique,
+        A: Autoincrement,
+        R: ForeignKey,
+    > SQLGeneratedStored
+    for SQLiteColumnBuilderGeneratedSet<DataType, ColumnType, DataMode, N, U, A, R>
+{
+    fn stored(mut self) -> Self {
+        if let Some(generated) = &mut self.generated {
+            generated.stored = true;
+        }
+        self
+    }
+}
+
+// References FieldSet
+type SQLiteColumnBuilderReferencesNotSet<
+    DataType,
+    ColumnType,
+    DataMode,
+    P,
+    N,
+    U,
+    A,
+    D,
+    F,
+    Fun,
+    UF,
+    UFun,
+    G,
+> = SQLiteColumn<
+    DataType,
+    ColumnType,
+    DataMode,
+    P,
+    N,
+    U,
+    A,
+    D,
+    F,
+    Fun,
+    UF,
+    UFun,
+    ReferenceNotSet,
+    G,
+>;
+
+type SQLiteColumnBuilderReferencesSet<
+    DataType,
+    ColumnType,
+    DataMode,
+    P,
+    N,
+    U,
+    A,
+    D,
+    F,
+    Fun,
+    UF,
+    UFun,
+    G,
+> = SQLiteColumn<DataType, ColumnType, DataMode, P, N, U, A, D, F, Fun, UF, UFun, ReferenceSet, G>;
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        A: Autoincrement,
+        D: DefaultValue,
+        F: DefaultFn,
+        Fun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        G: Generated,
+    > SQLReferences
+    for SQLiteColumnBuilderReferencesNotSet<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        N,
+        U,
+        A,
+        D,
+        F,
+        Fun,
+        UF,
+        UFun,
+        G,
+    >
+{
+    type DataType = DataType;
+    type Value = SQLiteColumnBuilderReferencesSet<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        N,
+        U,
+        A,
+        D,
+        F,
+        Fun,
+        UF,
+        UFun,
+        G,
+    >;
+
+    fn references<C, Func>(self, column: Func) -> Self::Value
+    where
+        C: SQLiteColumnData<DataType = Self::DataType> + ColumnBuilder,
+        Func: Fn() -> C + Send + Sync + 'static,
+    {
+        SQLiteColumn {
+            base: self.base,
+            default: self.default,
+            default_sql: self.default_sql,
+            default_fn: self.default_fn,
+            on_update_sql: self.on_update_sql,
+            on_update_fn: self.on_update_fn,
+            unique_name: self.unique_name,
+            primary_conflict: self.primary_conflict,
+            not_null_conflict: self.not_null_conflict,
+            unique_conflict: self.unique_conflict,
+            references: Some(SQLiteReference {
+                column: Arc::new(move || {
+                    let column = column();
+                    (column.table(), column.name())
+                }),
+                on_delete: None,
+                on_update: None,
+                deferrable: false,
+            }),
+            checks: self.checks,
+            generated: self.generated,
+            collation: self.collation,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        A: Autoincrement,
+        D: DefaultValue,
+        F: DefaultFn,
+        Fun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        G: Generated,
+    > SQLReferenceActions
+    for SQLiteColumnBuilderReferencesSet<
+        DataType,
+        ColumnType,
+        DataMode,
+        P,
+        N,
+        U,
+        A,
+        D,
+        F,
+        Fun,
+        UF,
+        UFun,
+        G,
+    >
+{
+    type Action = SQLiteReferenceAction;
+
+    fn on_delete(mut self, action: Self::Action) -> Self {
+        if let Some(references) = &mut self.references {
+            references.on_delete = Some(action);
+        }
+        self
+    }
+
+    fn on_update(mut self, action: Self::Action) -> Self {
+        if let Some(references) = &mut self.references {
+            references.on_update = Some(action);
+        }
+        self
+    }
+
+    fn deferrable(mut self) -> Self {
+        if let Some(references) = &mut self.references {
+            references.deferrable = true;
+        }
+        self
+    }
+}
+
+impl<
+        DataType: Clone,
+        DataMode: Default + Clone,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > SQLCollate
+    for SQLiteColumn<
+        DataType,
+        Text,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+{
+    type Collation = Collation;
+
+    fn collate(self, collation: impl Into<Self::Collation>) -> Self {
+        SQLiteColumn {
+            collation: Some(collation.into()),
+            ..self
+        }
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > SQLCheck
+    for SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+{
+    type Expression = SQL<SQLiteValue, bool>;
+
+    fn check<F>(mut self, name: &'static str, expression: F) -> Self
+    where
+        F: FnOnce(&Self) -> Self::Expression,
+    {
+        let expression = expression(&self);
+        self.checks.push((name, expression));
         self
     }
 }
+
+impl<
+        DataType: Clone,
+        ColumnType: SQLiteColumnType,
+        DataMode: SQLiteDataMode<DataType>,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > SQLiteDefinition
+    for SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+{
+    fn definition(self) -> Result<SQL<SQLiteValue>, SQLiteDefinitionError> {
+        let mut sql = SQL::name(self.base.name).push_str(format!(" {}", ColumnType::SQL_TYPE));
+
+        if let Some(collation) = self.collation {
+            sql = sql.push_str(" COLLATE ").append(collation.into_fragment());
+        }
+
+        if TPrimary::IS_PRIMARY {
+            sql = sql.push_str(" PRIMARY KEY");
+            if let Some(conflict) = self.primary_conflict {
+                sql = sql.push_str(format!(" ON CONFLICT {conflict}"));
+            }
+        }
+
+        if TAutoincrement::AUTOINCREMENT {
+            sql = sql.push_str(" AUTOINCREMENT");
+        }
+
+        if TNotNull::IS_NOT_NULL {
+            sql = sql.push_str(" NOT NULL");
+            if let Some(conflict) = self.not_null_conflict {
+                sql = sql.push_str(format!(" ON CONFLICT {conflict}"));
+            }
+        }
+
+        if TUnique::IS_UNIQUE {
+            if let Some(name) = self.unique_name {
+                sql = sql.push_str(" CONSTRAINT ").append(SQL::name(name));
+            }
+            sql = sql.push_str(" UNIQUE");
+            if let Some(conflict) = self.unique_conflict {
+                sql = sql.push_str(format!(" ON CONFLICT {conflict}"));
+            }
+        }
+
+        if let Some(generated) = self.generated {
+            let expression = (generated.expression)();
+            check_table(self.base.table, &expression.columns())?;
+
+            sql = sql
+                .push_str(" GENERATED ALWAYS AS ")
+                .nest(expression)
+                .push_str(if generated.stored {
+                    " STORED"
+                } else {
+                    " VIRTUAL"
+                });
+        }
+
+        if let Some(value) = self.default {
+            let value =
+                DataMode::to_sql_value(value).map_err(|source| SQLiteDefinitionError::Encode {
+                    column: self.base.name,
+                    source,
+                })?;
+            let value = DataMode::write_sql(value);
+
+            // SQLite only accepts a literal or a parenthesized expression as a default
+            sql = sql.push_str(" DEFAULT ");
+            sql = if matches!(value.chunks(), [SQLChunk::Param(_)]) {
+                sql.append(value)
+            } else {
+                sql.nest(value)
+            };
+        } else if let Some(expression) = self.default_sql {
+            sql = sql.push_str(format!(" DEFAULT ({expression})"));
+        }
+
+        for (name, expression) in self.checks {
+            // the expression was built before `in_table`, so the column refers to itself without one
+            let columns: Vec<_> = expression
+                .columns()
+                .into_iter()
+                .map(|(table, column)| match table {
+                    "" if column == self.base.name => (self.base.table, column),
+                    table => (table, column),
+                })
+                .collect();
+            check_table(self.base.table, &columns)?;
+
+            sql = sql
+                .push_str(" CONSTRAINT ")
+                .append(SQL::name(name))
+                .push_str(" CHECK ")
+                .nest(expression);
+        }
+
+        if let Some(references) = self.references {
+            let (table, column) = (references.column)();
+            sql = sql
+                .push_str(" REFERENCES ")
+                .append(SQL::table(table))
+                .push_str(" ")
+                .nest(SQL::<SQLiteValue>::name(column));
+
+            if let Some(action) = references.on_delete {
+                sql = sql.push_str(format!(" ON DELETE {action}"));
+            }
+            if let Some(action) = references.on_update {
+                sql = sql.push_str(format!(" ON UPDATE {action}"));
+            }
+            if references.deferrable {
+                sql = sql.push_str(" DEFERRABLE INITIALLY DEFERRED");
+            }
+        }
+
+        Ok(sql)
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > fmt::Display
+    for SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+where
+    Self: SQLiteDefinition,
+{
+    /// The column definition, or `fmt::Error` when `to_sql` fails.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sql = self.clone().to_sql().map_err(|_| fmt::Error)?;
+        f.write_str(&sql)
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > ColumnBuilder
+    for SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+{
+    fn name(&self) -> &'static str {
+        self.base.name
+    }
+
+    fn table(&self) -> &'static str {
+        self.base.table
+    }
+
+    fn in_table(mut self, table: &'static str) -> Self {
+        self.base.table = table;
+        self
+    }
+
+    fn build(self) -> Self {
+        self
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > SQLiteColumnRef
+    for SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+{
+    type DataType = DataType;
+
+    fn table_name(&self) -> &'static str {
+        self.base.table
+    }
+
+    fn column_name(&self) -> &'static str {
+        self.base.name
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > SQLiteColumnRef
+    for &SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+{
+    type DataType = DataType;
+
+    fn table_name(&self) -> &'static str {
+        self.base.table
+    }
+
+    fn column_name(&self) -> &'static str {
+        self.base.name
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > Expression<SQLiteValue>
+    for SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+{
+    type DataType = DataType;
+
+    fn into_sql(self) -> SQL<SQLiteValue, DataType> {
+        SQL::column(self.base.table, self.base.name).cast()
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > Expression<SQLiteValue>
+    for &SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+{
+    type DataType = DataType;
+
+    fn into_sql(self) -> SQL<SQLiteValue, DataType> {
+        SQL::column(self.base.table, self.base.name).cast()
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > SQLFragment<SQLiteValue>
+    for SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+{
+    fn into_fragment(self) -> SQL<SQLiteValue> {
+        SQL::column(self.base.table, self.base.name)
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > SQLFragment<SQLiteValue>
+    for &SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+{
+    fn into_fragment(self) -> SQL<SQLiteValue> {
+        SQL::column(self.base.table, self.base.name)
+    }
+}
+
+// SQLite has no array type: JSON columns holding a `Vec` are queried as arrays through
+// `json_each`, which reads JSON text and JSONB alike.
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<Vec<DataType>, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<Vec<DataType>, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > ArrayExpression<SQLiteValue>
+    for SQLiteColumn<
+        Vec<DataType>,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+where
+    Self: SQLiteJSONExpression,
+{
+    type Element = DataType;
+
+    fn contains(self, values: Vec<SQL<SQLiteValue>>) -> SQL<SQLiteValue, bool> {
+        json_array_contains(self.into_sql().cast(), values)
+    }
+
+    fn contained(self, values: Vec<SQL<SQLiteValue>>) -> SQL<SQLiteValue, bool> {
+        json_array_contained(self.into_sql().cast(), values)
+    }
+
+    fn overlaps(self, values: Vec<SQL<SQLiteValue>>) -> SQL<SQLiteValue, bool> {
+        json_array_overlaps(self.into_sql().cast(), values)
+    }
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: Default + Clone,
+        TPrimary: PrimaryKey,
+        TNotNull: NotNull,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<Vec<DataType>, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<Vec<DataType>, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > ArrayExpression<SQLiteValue>
+    for &SQLiteColumn<
+        Vec<DataType>,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+where
+    Self: SQLiteJSONExpression,
+{
+    type Element = DataType;
+
+    fn contains(self, values: Vec<SQL<SQLiteValue>>) -> SQL<SQLiteValue, bool> {
+        json_array_contains(self.into_sql().cast(), values)
+    }
+
+    fn contained(self, values: Vec<SQL<SQLiteValue>>) -> SQL<SQLiteValue, bool> {
+        json_array_contained(self.into_sql().cast(), values)
+    }
+
+    fn overlaps(self, values: Vec<SQL<SQLiteValue>>) -> SQL<SQLiteValue, bool> {
+        json_array_overlaps(self.into_sql().cast(), values)
+    }
+}
+
+fn json_each(array: SQL<SQLiteValue>) -> SQL<SQLiteValue> {
+    SQL::raw("SELECT value FROM json_each(")
+        .append(array)
+        .push_str(")")
+}
+
+fn json_array_contains(
+    array: SQL<SQLiteValue>,
+    values: Vec<SQL<SQLiteValue>>,
+) -> SQL<SQLiteValue, bool> {
+    let values = SQL::raw("json_array(")
+        .append(SQL::join(values, ", "))
+        .push_str(")");
+
+    not_exists(
+        json_each(values)
+            .push_str(" WHERE value NOT IN ")
+            .nest(json_each(array)),
+    )
+}
+
+fn json_array_contained(
+    array: SQL<SQLiteValue>,
+    values: Vec<SQL<SQLiteValue>>,
+) -> SQL<SQLiteValue, bool> {
+    // SQLite accepts an empty `IN ()`, which is what an empty list of values means here.
+    not_exists(
+        json_each(array)
+            .push_str(" WHERE value NOT IN ")
+            .nest(SQL::join(values, ", ")),
+    )
+}
+
+fn json_array_overlaps(
+    array: SQL<SQLiteValue>,
+    values: Vec<SQL<SQLiteValue>>,
+) -> SQL<SQLiteValue, bool> {
+    exists(
+        json_each(array)
+            .push_str(" WHERE value IN ")
+            .nest(SQL::join(values, ", ")),
+    )
+}
+
+impl<
+        DataType: Clone,
+        ColumnType: Default + Clone,
+        DataMode: SQLiteDataMode<DataType>,
+        TPrimary: PrimaryKey,
+        TNotNull: Nullability,
+        TUnique: Unique,
+        TAutoincrement: Autoincrement,
+        TDefault: DefaultValue,
+        TDefaultFn: DefaultFn,
+        TFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TUpdateFn: UpdateFn,
+        TUpdateFunc: Fn() -> Result<DataType, std::fmt::Error> + Clone,
+        TReference: ForeignKey,
+        TGenerated: Generated,
+    > SQLiteColumnData
+    for SQLiteColumn<
+        DataType,
+        ColumnType,
+        DataMode,
+        TPrimary,
+        TNotNull,
+        TUnique,
+        TAutoincrement,
+        TDefault,
+        TDefaultFn,
+        TFunc,
+        TUpdateFn,
+        TUpdateFunc,
+        TReference,
+        TGenerated,
+    >
+{
+    type DataType = DataType;
+    type Writable = TGenerated::Writable<DataType>;
+    type Assignable = TGenerated::Writable<TNotNull::Assignable<DataType>>;
+
+    fn insert_value(
+        &self,
+        value: Option<Self::Writable>,
+    ) -> Result<Option<SQL<SQLiteValue>>, SQLiteInsertError> {
+        let value = match (value, &self.default_fn) {
+            (Some(value), _) => TGenerated::written(value),
+            (None, Some(default_fn)) => {
+                default_fn().map_err(|source| SQLiteInsertError::DefaultFn {
+                    column: self.base.name,
+                    source,
+                })?
+            }
+            (None, None) => return Ok(None),
+        };
+
+        DataMode::to_sql_value(value)
+            .map(|value| Some(DataMode::write_sql(value)))
+            .map_err(|source| SQLiteInsertError::Encode {
+                column: self.base.name,
+                source,
+            })
+    }
+
+    fn update_value(
+        &self,
+        value: Option<Self::Assignable>,
+    ) -> Result<Option<SQL<SQLiteValue>>, SQLiteUpdateError> {
+        let value = match (value, &self.on_update_fn) {
+            (Some(value), _) => match TNotNull::assigned(TGenerated::written(value)) {
+                Some(value) => value,
+                None => return Ok(Some(SQL::raw("NULL"))),
+            },
+            (None, Some(on_update_fn)) => {
+                on_update_fn().map_err(|source| SQLiteUpdateError::OnUpdateFn {
+                    column: self.base.name,
+                    source,
+                })?
+            }
+            (None, None) => {
+                return Ok(self
+                    .on_update_sql
+                    .map(|sql| SQL::default().nest(SQL::raw(sql))))
+            }
+        };
+
+        DataMode::to_sql_value(value)
+            .map(|value| Some(DataMode::write_sql(value)))
+            .map_err(|source| SQLiteUpdateError::Encode {
+                column: self.base.name,
+                source,
+            })
+    }
+
+    fn read_sql(&self) -> SQL<SQLiteValue, DataType> {
+        DataMode::read_sql(SQL::column(self.base.table, self.base.name)).cast()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn column_on_conflict() {
+        crate::sqlite_table!("members", {
+            id: integer("id", SQLiteIntegerMode::Number).primary().primary_on_conflict(SQLiteConflict::Replace),
+            email: text("email", SQLiteTextMode::String).unique("members_email_unique").unique_on_conflict(SQLiteConflict::Ignore),
+            name: text("name", SQLiteTextMode::String).not_null().not_null_on_conflict(SQLiteConflict::Abort),
+        });
+
+        let members = Members::new().to_sql().unwrap();
+
+        assert_eq!(
+            members,
+            concat!(
+                r#"CREATE TABLE "members" ("id" INTEGER PRIMARY KEY ON CONFLICT REPLACE, "#,
+                r#""email" TEXT CONSTRAINT "members_email_unique" UNIQUE ON CONFLICT IGNORE, "#,
+                r#""name" TEXT NOT NULL ON CONFLICT ABORT)"#
+            )
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&members, ()).unwrap();
+        conn.execute(
+            r#"INSERT INTO "members" VALUES (1, 'a@example.com', 'a')"#,
+            (),
+        )
+        .unwrap();
+        conn.execute(
+            r#"INSERT INTO "members" VALUES (1, 'b@example.com', 'b')"#,
+            (),
+        )
+        .unwrap();
+        conn.execute(
+            r#"INSERT INTO "members" VALUES (2, 'b@example.com', 'c')"#,
+            (),
+        )
+        .unwrap();
+
+        let names: Vec<String> = conn
+            .prepare(r#"SELECT "name" FROM "members" ORDER BY "id""#)
+            .unwrap()
+            .query_map((), |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(names, ["b"]);
+    }
+
+    #[test]
+    fn foreign_keys() {
+        crate::sqlite_table!("authors", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            mentor_id: integer("mentor_id", SQLiteIntegerMode::Number).references(|| Authors::new().id),
+        });
+
+        crate::sqlite_table!("books", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            author_id: integer("author_id", SQLiteIntegerMode::Number)
+                .not_null()
+                .references(|| Authors::new().id)
+                .on_delete(SQLiteReferenceAction::Cascade)
+                .on_update(SQLiteReferenceAction::NoAction)
+                .deferrable(),
+        });
+
+        let authors = Authors::new().to_sql().unwrap();
+        let books = Books::new().to_sql().unwrap();
+
+        assert_eq!(
+            authors,
+            r#"CREATE TABLE "authors" ("id" INTEGER PRIMARY KEY, "mentor_id" INTEGER REFERENCES "authors" ("id"))"#
+        );
+        assert_eq!(
+            books,
+            concat!(
+                r#"CREATE TABLE "books" ("id" INTEGER PRIMARY KEY, "#,
+                r#""author_id" INTEGER NOT NULL REFERENCES "authors" ("id") ON DELETE CASCADE ON UPDATE NO ACTION DEFERRABLE INITIALLY DEFERRED)"#
+            )
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute("PRAGMA foreign_keys = ON", ()).unwrap();
+        conn.execute(&authors, ()).unwrap();
+        conn.execute(&books, ()).unwrap();
+        conn.execute(r#"INSERT INTO "authors" VALUES (1, NULL), (2, 1)"#, ())
+            .unwrap();
+        conn.execute(r#"INSERT INTO "books" VALUES (1, 1), (2, 2)"#, ())
+            .unwrap();
+        assert!(conn
+            .execute(r#"INSERT INTO "books" VALUES (3, 3)"#, ())
+            .is_err());
+
+        conn.execute(r#"DELETE FROM "authors" WHERE "id" = 2"#, ())
+            .unwrap();
+        let books: i64 = conn
+            .query_row(r#"SELECT count(*) FROM "books""#, (), |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(books, 1);
+    }
+
+    #[test]
+    fn generated_columns() {
+        use crate::expressions::{concat, mul};
+
+        crate::sqlite_table!("line_items", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            price: integer("price", SQLiteIntegerMode::Number).not_null(),
+            quantity: integer("quantity", SQLiteIntegerMode::Number).not_null(),
+            sku: text("sku", SQLiteTextMode::String).not_null(),
+            total: integer("total", SQLiteIntegerMode::Number)
+                .generated_always_as(|items: &LineItems| mul(&items.price, &items.quantity))
+                .stored(),
+            label: text("label", SQLiteTextMode::String)
+                .generated_always_as(|items: &LineItems| concat(&items.sku, "-item")),
+        });
+
+        let line_items = LineItems::new();
+
+        assert_eq!(
+            line_items.clone().to_sql().unwrap(),
+            concat!(
+                r#"CREATE TABLE "line_items" ("id" INTEGER PRIMARY KEY, "price" INTEGER NOT NULL, "#,
+                r#""quantity" INTEGER NOT NULL, "sku" TEXT NOT NULL, "#,
+                r#""total" INTEGER GENERATED ALWAYS AS ("price" * "quantity") STORED, "#,
+                r#""label" TEXT GENERATED ALWAYS AS ("sku" || '-item') VIRTUAL)"#
+            )
+        );
+
+        let statement = insert(&line_items)
+            .values(InsertLineItems {
+                price: Some(250),
+                quantity: Some(4),
+                sku: Some("mug".to_string()),
+                total: None,
+                ..Default::default()
+            })
+            .unwrap();
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&line_items.to_sql().unwrap(), ()).unwrap();
+        conn.execute(
+            &statement.clone().to_sql(),
+            rusqlite::params_from_iter(statement.params()),
+        )
+        .unwrap();
+
+        let row: (i64, String) = conn
+            .query_row(r#"SELECT "total", "label" FROM "line_items""#, (), |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+
+        assert_eq!(row, (1000, "mug-item".to_string()));
+
+        crate::sqlite_table!("invoices", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            total: integer("total", SQLiteIntegerMode::Number)
+                .generated_always_as(|items: &LineItems| mul(&items.price, 2)),
+        });
+
+        assert_eq!(
+            Invoices::new().to_sql(),
+            Err(crate::common::SQLiteDefinitionError::OtherTable {
+                table: "invoices",
+                column: ("line_items", "price"),
+            })
+        );
+    }
+
+    #[test]
+    fn column_defaults() {
+        crate::sqlite_table!("defaults", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            count: integer("count", SQLiteIntegerMode::Number).default(i64::MIN),
+            ratio: real("ratio").default(0.1),
+            label: text("label", SQLiteTextMode::String).default("it's \"quoted\"".to_string()),
+            bytes: blob("bytes", SQLiteBlobMode::Buffer).default(vec![0x00, 0xFF, 0x27]),
+        });
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&Defaults::new().to_sql().unwrap(), ())
+            .unwrap();
+        conn.execute(r#"INSERT INTO "defaults" DEFAULT VALUES"#, ())
+            .unwrap();
+
+        let row: (i64, f64, String, Vec<u8>) = conn
+            .query_row(
+                r#"SELECT "count", "ratio", "label", "bytes" FROM "defaults""#,
+                (),
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+
+        assert_eq!(
+            row,
+            (
+                i64::MIN,
+                0.1,
+                "it's \"quoted\"".to_string(),
+                vec![0x00, 0xFF, 0x27]
+            )
+        );
+
+        crate::sqlite_table!("measurements", {
+            value: real("value").default(f64::NAN),
+        });
+
+        let measurements = Measurements::new();
+
+        assert!(measurements.value.default.is_some_and(f64::is_nan));
+        assert!(measurements.to_sql().is_err());
+    }
+
+    #[test]
+    fn column_default_sql() {
+        crate::sqlite_table!("events", {
+            id: text("id", SQLiteTextMode::String)
+                .primary()
+                .default_sql(DefaultSQL::raw("lower(hex(randomblob(16)))")),
+            created_at: text("created_at", SQLiteTextMode::String).not_null().default_sql(CURRENT_TIMESTAMP),
+            created_unix: integer("created_unix", SQLiteIntegerMode::Number).default_sql(UNIXEPOCH),
+            updated_at: integer("updated_at", SQLiteIntegerMode::Timestamp).default_sql(UNIXEPOCH).on_update_sql(UNIXEPOCH),
+        });
+
+        let events = Events::new().to_sql().unwrap();
+        let touch = update(&Events::new())
+            .set(UpdateEvents {
+                created_at: Some("2024-01-01 00:00:00".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            events,
+            concat!(
+                r#"CREATE TABLE "events" ("id" TEXT PRIMARY KEY DEFAULT (lower(hex(randomblob(16)))), "#,
+                r#""created_at" TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP), "#,
+                r#""created_unix" INTEGER DEFAULT (unixepoch()), "#,
+                r#""updated_at" INTEGER DEFAULT (unixepoch()))"#
+            )
+        );
+        assert_eq!(
+            touch.clone().to_sql(),
+            r#"UPDATE "events" SET "created_at" = ?, "updated_at" = (unixepoch())"#
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&events, ()).unwrap();
+        conn.execute(r#"INSERT INTO "events" DEFAULT VALUES"#, ())
+            .unwrap();
+
+        conn.execute(
+            &touch.clone().to_sql(),
+            rusqlite::params_from_iter(touch.params()),
+        )
+        .unwrap();
+
+        let (id, created_at, created_unix, updated_at): (String, String, i64, i64) = conn
+            .query_row(
+                r#"SELECT "id", "created_at", "created_unix", "updated_at" FROM "events""#,
+                (),
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+
+        assert_eq!(id.len(), 32);
+        assert_eq!(created_at, "2024-01-01 00:00:00");
+        assert!(created_unix > 0);
+        assert!(
+            SQLiteIntegerMode::Timestamp::from_sql_value(crate::common::SQLiteValue::Integer(
+                updated_at
+            ))
+            .is_ok_and(|updated_at| updated_at > std::time::UNIX_EPOCH)
+        );
+    }
+}
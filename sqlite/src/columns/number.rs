@@ -6,17 +6,21 @@ use common::{
 use crate::common::Number;
 
 use super::{
-    integer::NotAutoIncremented, DefaultFnNotSet, DefaultNotSet, NotPrimary, NotUnique, Nullable,
-    SQLiteColumn,
+    integer::NotAutoIncremented, DefaultFnNotSet, DefaultNotSet, GeneratedNotSet, NotPrimary,
+    NotUnique, Nullable, ReferenceNotSet, SQLiteColumn, UpdateFnNotSet,
 };
 
-type SQLiteNumberColumn<
+pub type SQLiteNumberColumn<
     TPrimary = NotPrimary,
     TNotNull = Nullable,
     TUnique = NotUnique,
     TDefault = DefaultNotSet,
     TDefaultFn = DefaultFnNotSet,
     TFunc = fn() -> Result<Number, std::fmt::Error>,
+    TUpdateFn = UpdateFnNotSet,
+    TUpdateFunc = fn() -> Result<Number, std::fmt::Error>,
+    TReference = ReferenceNotSet,
+    TGenerated = GeneratedNotSet,
 > = SQLiteColumn<
     Number,
     Number,
@@ -28,9 +32,13 @@ type SQLiteNumberColumn<
     TDefault,
     TDefaultFn,
     TFunc,
+    TUpdateFn,
+    TUpdateFunc,
+    TReference,
+    TGenerated,
 >;
 
-pub(crate) fn number(name: &'static str) -> SQLiteNumberColumn {
+pub fn number(name: &'static str) -> SQLiteNumberColumn {
     SQLiteNumberColumn {
         base: ColumnBaseBuilder {
             name,
@@ -60,19 +68,32 @@ impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Compar
 
 #[cfg(test)]
 mod test {
+    use common::traits::{SQLDefault, SQLNotNull};
 
-    // #[test]
-    // fn builder() {
-    //     let str = 12.0;
-    //     let int = real("id").primary().not_null().default(str);
+    use crate::{common::Number, traits::table::SQLiteDefinition};
 
-    //     std::thread::spawn(move || {
-    //         let int = int;
-    //         assert_eq!(int.base.default, Some(12.0));
-    //     });
+    use super::number;
 
-    // .autoincrement()
-    // .not_null()
-    // .default(42);
-    // }
+    #[test]
+    fn builder() {
+        let column = number("amount").not_null().default(Number::Int(12));
+
+        assert!(matches!(column.default, Some(Number::Int(12))));
+    }
+
+    #[test]
+    fn to_sql() {
+        assert_eq!(number("amount").to_sql().unwrap(), r#""amount" NUMERIC"#);
+        assert_eq!(
+            number("amount").not_null().to_sql().unwrap(),
+            r#""amount" NUMERIC NOT NULL"#
+        );
+        assert_eq!(
+            number("amount")
+                .default(Number::Real(0.25))
+                .to_sql()
+                .unwrap(),
+            r#""amount" NUMERIC DEFAULT 0.25"#
+        );
+    }
 }
@@ -6,17 +6,21 @@ use common::{
 use crate::common::Real;
 
 use super::{
-    integer::NotAutoIncremented, DefaultFnNotSet, DefaultNotSet, NotPrimary, NotUnique, Nullable,
-    SQLiteColumn,
+    integer::NotAutoIncremented, DefaultFnNotSet, DefaultNotSet, GeneratedNotSet, NotPrimary,
+    NotUnique, Nullable, ReferenceNotSet, SQLiteColumn, UpdateFnNotSet,
 };
 
-type SQLiteRealColumn<
+pub type SQLiteRealColumn<
     TPrimary = NotPrimary,
     TNotNull = Nullable,
     TUnique = NotUnique,
     TDefault = DefaultNotSet,
     TDefaultFn = DefaultFnNotSet,
     TFunc = fn() -> Result<f64, std::fmt::Error>,
+    TUpdateFn = UpdateFnNotSet,
+    TUpdateFunc = fn() -> Result<f64, std::fmt::Error>,
+    TReference = ReferenceNotSet,
+    TGenerated = GeneratedNotSet,
 > = SQLiteColumn<
     f64,
     Real,
@@ -28,9 +32,13 @@ type SQLiteRealColumn<
     TDefault,
     TDefaultFn,
     TFunc,
+    TUpdateFn,
+    TUpdateFunc,
+    TReference,
+    TGenerated,
 >;
 
-pub(crate) fn real(name: &'static str) -> SQLiteRealColumn {
+pub fn real(name: &'static str) -> SQLiteRealColumn {
     SQLiteRealColumn {
         base: ColumnBaseBuilder {
             name,
@@ -60,21 +68,60 @@ impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Compar
 
 #[cfg(test)]
 mod test {
+    use std::fmt::Write;
+
+    use common::{
+        traits::{SQLDefault, SQLNotNull, SQLPrimary, SQLUnique},
+        InlineError,
+    };
+
+    use crate::{
+        common::{SQLiteDefinitionError, SQLiteValue},
+        traits::table::SQLiteDefinition,
+    };
+
     use super::real;
-    use core::panic;
 
-    // #[test]
-    // fn builder() {
-    //     let str = 12.0;
-    //     let int = real("id").primary().not_null().default(str);
+    #[test]
+    fn builder() {
+        let value = 12.0;
+        let column = real("id").primary().not_null().default(value);
+
+        std::thread::spawn(move || {
+            let column = column;
+            assert_eq!(column.default, Some(12.0));
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn to_sql() {
+        assert_eq!(real("score").to_sql().unwrap(), r#""score" REAL"#);
+        assert_eq!(
+            real("score")
+                .not_null()
+                .unique("score_unique")
+                .to_sql()
+                .unwrap(),
+            r#""score" REAL NOT NULL CONSTRAINT "score_unique" UNIQUE"#
+        );
+        assert_eq!(
+            real("score").default(-1.5).to_sql().unwrap(),
+            r#""score" REAL DEFAULT -1.5"#
+        );
+    }
 
-    //     std::thread::spawn(move || {
-    //         let int = int;
-    //         assert_eq!(int.base.default, Some(12.0));
-    //     });
+    #[test]
+    fn non_finite_default() {
+        let column = real("score").default(f64::INFINITY);
 
-    // .autoincrement()
-    // .not_null()
-    // .default(42);
-    // }
+        assert!(write!(String::new(), "{column}").is_err());
+        assert_eq!(
+            column.to_sql(),
+            Err(SQLiteDefinitionError::Inline(InlineError::Literal(
+                SQLiteValue::Real(f64::INFINITY)
+            )))
+        );
+    }
 }
@@ -1,36 +1,138 @@
-use std::{fmt, marker::PhantomData};
-
 use common::{
     builders::column::ColumnBaseBuilder,
-    traits::{Comparable, DefaultFn, DefaultValue, NotNull, PrimaryKey, Unique},
+    traits::{
+        Comparable, DefaultFn, DefaultValue, ForeignKey, NotNull, PrimaryKey, Unique, UpdateFn,
+    },
+    SQL,
 };
 
-use crate::common::Text;
+use crate::{
+    common::{SQLiteValue, Text},
+    expressions::SQLiteJSONExpression,
+    traits::column::{Generated, SQLCheckValues, SQLiteDataMode},
+};
 
 use super::{
-    integer::NotAutoIncremented, DefaultFnNotSet, DefaultNotSet, NotPrimary, NotUnique, Nullable,
-    SQLiteColumn,
+    integer::NotAutoIncremented, DefaultFnNotSet, DefaultNotSet, GeneratedNotSet, NotPrimary,
+    NotUnique, Nullable, ReferenceNotSet, SQLiteColumn, UpdateFnNotSet,
 };
 
-#[derive(Debug, Default, Clone, Copy)]
-pub enum SQLiteTextMode {
-    #[default]
-    String,
-    Enum,
-    JSON,
+/// Mode of a text column, with the Rust type its columns hold unless one is given explicitly.
+pub trait TextMode: Copy + Default {
+    type DataType: Clone;
+}
+
+/// Rust enum stored in a `SQLiteTextMode::Enum` column as the text of its variant, usually
+/// implemented with [`sqlite_enum!`](crate::sqlite_enum).
+pub trait SQLiteEnum: Sized + Clone {
+    /// Text of every variant, in declaration order.
+    const VALUES: &'static [&'static str];
+
+    fn as_text(&self) -> &'static str;
+    fn from_text(text: &str) -> Option<Self>;
 }
 
-type SQLiteTextColumn<
+/// Modes of text columns. Each is a type, so the mode picks how the column's Rust type is stored,
+/// e.g. `text("meta", SQLiteTextMode::JSON)` holds any `serde` type as JSON text.
+#[allow(non_snake_case)]
+pub mod SQLiteTextMode {
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use crate::{
+        common::{SQLiteDecodeError, SQLiteEncodeError, SQLiteValue, Text},
+        traits::column::SQLiteDataMode,
+    };
+
+    use super::{SQLiteEnum, TextMode};
+
+    /// Plain `String`.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct String;
+
+    /// A [`SQLiteEnum`], stored as the text of its variant. The enum type must be given
+    /// explicitly, e.g. `text::<Role>("role", SQLiteTextMode::Enum)`.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Enum;
+
+    /// Any `serde` type, stored as its JSON text; `serde_json::Value` unless given explicitly.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct JSON;
+
+    fn text(value: SQLiteValue) -> Result<Text, SQLiteDecodeError> {
+        match value {
+            SQLiteValue::Text(value) => Ok(value),
+            value => Err(SQLiteDecodeError::new("TEXT", value)),
+        }
+    }
+
+    impl TextMode for String {
+        type DataType = Text;
+    }
+
+    impl SQLiteDataMode<Text> for String {
+        fn to_sql_value(value: Text) -> Result<SQLiteValue, SQLiteEncodeError> {
+            Ok(SQLiteValue::Text(value))
+        }
+
+        fn from_sql_value(value: SQLiteValue) -> Result<Text, SQLiteDecodeError> {
+            text(value)
+        }
+    }
+
+    impl TextMode for Enum {
+        type DataType = Text;
+    }
+
+    impl<T: SQLiteEnum> SQLiteDataMode<T> for Enum {
+        fn to_sql_value(value: T) -> Result<SQLiteValue, SQLiteEncodeError> {
+            Ok(SQLiteValue::Text(value.as_text().to_string()))
+        }
+
+        fn from_sql_value(value: SQLiteValue) -> Result<T, SQLiteDecodeError> {
+            let value = text(value)?;
+
+            T::from_text(&value)
+                .ok_or_else(|| SQLiteDecodeError::new("enum variant", SQLiteValue::Text(value)))
+        }
+    }
+
+    impl TextMode for JSON {
+        type DataType = serde_json::Value;
+    }
+
+    impl<T: Serialize + DeserializeOwned + Clone> SQLiteDataMode<T> for JSON {
+        fn to_sql_value(value: T) -> Result<SQLiteValue, SQLiteEncodeError> {
+            serde_json::to_string(&value)
+                .map(SQLiteValue::Text)
+                .map_err(|error| SQLiteEncodeError::new("JSON text", error))
+        }
+
+        fn from_sql_value(value: SQLiteValue) -> Result<T, SQLiteDecodeError> {
+            let json = text(value)?;
+
+            serde_json::from_str(&json)
+                .map_err(|_| SQLiteDecodeError::new("JSON text", SQLiteValue::Text(json)))
+        }
+    }
+}
+
+pub type SQLiteTextColumn<
+    TMode = SQLiteTextMode::String,
+    TData = <TMode as TextMode>::DataType,
     TPrimary = NotPrimary,
     TNotNull = Nullable,
     TUnique = NotUnique,
     TDefault = DefaultNotSet,
     TDefaultFn = DefaultFnNotSet,
-    Func = fn() -> Result<String, std::fmt::Error>,
+    Func = fn() -> Result<TData, std::fmt::Error>,
+    TUpdateFn = UpdateFnNotSet,
+    TUpdateFunc = fn() -> Result<TData, std::fmt::Error>,
+    TReference = ReferenceNotSet,
+    TGenerated = GeneratedNotSet,
 > = SQLiteColumn<
-    String,
+    TData,
     Text,
-    SQLiteTextMode,
+    TMode,
     TPrimary,
     TNotNull,
     TUnique,
@@ -38,9 +140,17 @@ type SQLiteTextColumn<
     TDefault,
     TDefaultFn,
     Func,
+    TUpdateFn,
+    TUpdateFunc,
+    TReference,
+    TGenerated,
 >;
 
-pub(crate) fn text(name: &'static str, mode: SQLiteTextMode) -> SQLiteTextColumn {
+/// Text column in `mode`, holding `T`; `T` is inferred when the mode stores a single type.
+pub fn text<T: Clone, M: TextMode + SQLiteDataMode<T>>(
+    name: &'static str,
+    mode: M,
+) -> SQLiteTextColumn<M, T> {
     SQLiteTextColumn {
         base: ColumnBaseBuilder {
             name,
@@ -51,50 +161,437 @@ pub(crate) fn text(name: &'static str, mode: SQLiteTextMode) -> SQLiteTextColumn
     }
 }
 
-impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Comparable<String>
-    for SQLiteTextColumn<P, N, U, D, F>
+impl<M: TextMode, P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn>
+    Comparable<String> for SQLiteTextColumn<M, String, P, N, U, D, F>
+{
+}
+impl<M: TextMode, P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn>
+    Comparable<&String> for SQLiteTextColumn<M, String, P, N, U, D, F>
+{
+}
+
+impl<M: TextMode, P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn>
+    Comparable<&str> for SQLiteTextColumn<M, String, P, N, U, D, F>
 {
 }
-impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Comparable<&String>
-    for SQLiteTextColumn<P, N, U, D, F>
+
+impl<
+        M: TextMode,
+        T: Clone,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        D: DefaultValue,
+        F: DefaultFn,
+    > Comparable<Self> for SQLiteTextColumn<M, T, P, N, U, D, F>
 {
 }
 
-impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Comparable<&str>
-    for SQLiteTextColumn<P, N, U, D, F>
+impl<
+        M: TextMode,
+        T: Clone,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        D: DefaultValue,
+        F: DefaultFn,
+    > Comparable<Self> for &SQLiteTextColumn<M, T, P, N, U, D, F>
 {
 }
 
-impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Comparable<Self>
-    for SQLiteTextColumn<P, N, U, D, F>
+impl<
+        T: Clone,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        D: DefaultValue,
+        F: DefaultFn,
+        Fun: Fn() -> Result<T, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<T, std::fmt::Error> + Clone,
+        R: ForeignKey,
+        G: Generated,
+    > SQLiteJSONExpression
+    for SQLiteTextColumn<SQLiteTextMode::JSON, T, P, N, U, D, F, Fun, UF, UFun, R, G>
 {
 }
 
-impl<P: PrimaryKey, N: NotNull, U: Unique, D: DefaultValue, F: DefaultFn> Comparable<Self>
-    for &SQLiteTextColumn<P, N, U, D, F>
+impl<
+        T: Clone,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        D: DefaultValue,
+        F: DefaultFn,
+        Fun: Fn() -> Result<T, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<T, std::fmt::Error> + Clone,
+        R: ForeignKey,
+        G: Generated,
+    > SQLiteJSONExpression
+    for &SQLiteTextColumn<SQLiteTextMode::JSON, T, P, N, U, D, F, Fun, UF, UFun, R, G>
 {
 }
 
+impl<
+        T: SQLiteEnum,
+        P: PrimaryKey,
+        N: NotNull,
+        U: Unique,
+        D: DefaultValue,
+        F: DefaultFn,
+        Fun: Fn() -> Result<T, std::fmt::Error> + Clone,
+        UF: UpdateFn,
+        UFun: Fn() -> Result<T, std::fmt::Error> + Clone,
+        R: ForeignKey,
+        G: Generated,
+    > SQLCheckValues
+    for SQLiteTextColumn<SQLiteTextMode::Enum, T, P, N, U, D, F, Fun, UF, UFun, R, G>
+{
+    fn check_values(mut self, name: &'static str) -> Self {
+        let values = T::VALUES
+            .iter()
+            .map(|value| SQL::param(SQLiteValue::Text(value.to_string())));
+
+        self.checks.push((
+            name,
+            SQL::column(self.base.table, self.base.name)
+                .push_str(" IN ")
+                .nest(SQL::<SQLiteValue>::join(values, ", "))
+                .cast(),
+        ));
+        self
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{text, SQLiteTextMode};
-    use core::panic;
-
-    // #[test]
-    // fn builder() {
-    //     let str = "my text";
-    //     let int = text("id", SQLiteTextMode::String)
-    //         .primary()
-    //         .not_null()
-    //         .default(str);
-
-    //     std::thread::spawn(move || {
-    //         let int = int;
-    //         assert_eq!(int.base.default, Some(str));
-    //     });
-
-    //     // .autoincrement()
-    //     // .not_null()
-    //     // .default(42);
-    // }
+    use std::collections::BTreeMap;
+
+    use common::traits::{SQLDefault, SQLDefaultSQL, SQLNotNull, SQLPrimary, SQLUnique};
+
+    use serde::{Deserialize, Serialize};
+
+    use common::InlineError;
+
+    use crate::{
+        common::{Collation, SQLiteDefinitionError, SQLiteEncodeError, SQLiteValue, CURRENT_DATE},
+        prelude::*,
+        sqlite_enum,
+        traits::{
+            column::{SQLCheckValues, SQLCollate, SQLiteDataMode},
+            table::SQLiteDefinition,
+        },
+    };
+
+    use super::{text, SQLiteEnum, SQLiteTextMode};
+
+    sqlite_enum! {
+        #[derive(Debug, PartialEq)]
+        enum Status {
+            Active = "active",
+            OnHold = "on hold",
+            Closed = "it's closed",
+        }
+    }
+
+    sqlite_enum! {
+        #[derive(Debug, PartialEq)]
+        enum Separator {
+            Nul = "\0",
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Meta {
+        age: i64,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn builder() {
+        let value = "my text";
+        let column = text("id", SQLiteTextMode::String)
+            .primary()
+            .not_null()
+            .default(value.to_string());
+
+        std::thread::spawn(move || {
+            let column = column;
+            assert_eq!(column.default.as_deref(), Some(value));
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn to_sql() {
+        assert_eq!(
+            text("id", SQLiteTextMode::String)
+                .primary()
+                .to_sql()
+                .unwrap(),
+            r#""id" TEXT PRIMARY KEY"#
+        );
+        assert_eq!(
+            text("email", SQLiteTextMode::String)
+                .unique("email_unique")
+                .not_null()
+                .to_string(),
+            r#""email" TEXT NOT NULL CONSTRAINT "email_unique" UNIQUE"#
+        );
+        assert_eq!(
+            text("name", SQLiteTextMode::String)
+                .default("it's".to_string())
+                .to_sql()
+                .unwrap(),
+            r#""name" TEXT DEFAULT 'it''s'"#
+        );
+        assert_eq!(
+            text("day", SQLiteTextMode::String)
+                .default_sql(CURRENT_DATE)
+                .to_sql()
+                .unwrap(),
+            r#""day" TEXT DEFAULT (CURRENT_DATE)"#
+        );
+        assert_eq!(
+            text("name", SQLiteTextMode::String)
+                .collate(Collation::RTrim)
+                .not_null()
+                .to_sql()
+                .unwrap(),
+            r#""name" TEXT COLLATE RTRIM NOT NULL"#
+        );
+    }
+
+    #[test]
+    fn json() {
+        let meta = Meta {
+            age: 30,
+            tags: vec!["admin".to_string()],
+        };
+        let value = SQLiteTextMode::JSON::to_sql_value(meta.clone()).unwrap();
+
+        assert_eq!(
+            value,
+            SQLiteValue::Text(r#"{"age":30,"tags":["admin"]}"#.to_string())
+        );
+        assert_eq!(
+            SQLiteTextMode::JSON::from_sql_value(value),
+            Ok(meta.clone())
+        );
+        assert!(
+            <SQLiteTextMode::JSON as SQLiteDataMode<Meta>>::from_sql_value(SQLiteValue::Text(
+                "[1, 2]".to_string()
+            ))
+            .is_err()
+        );
+        assert_eq!(
+            text::<Meta, _>("meta", SQLiteTextMode::JSON)
+                .not_null()
+                .default(meta)
+                .to_sql()
+                .unwrap(),
+            r#""meta" TEXT NOT NULL DEFAULT '{"age":30,"tags":["admin"]}'"#
+        );
+    }
+
+    #[test]
+    fn json_encode_error() {
+        // JSON object keys must be strings, so maps keyed by tuples cannot be stored
+        let grid = BTreeMap::from([((0, 0), 1)]);
+        let error = SQLiteEncodeError::new("JSON text", "key must be a string");
+
+        assert_eq!(
+            SQLiteTextMode::JSON::to_sql_value(grid.clone()),
+            Err(error.clone())
+        );
+        assert_eq!(
+            text::<BTreeMap<(i32, i32), i32>, _>("grid", SQLiteTextMode::JSON)
+                .default(grid)
+                .to_sql(),
+            Err(SQLiteDefinitionError::Encode {
+                column: "grid",
+                source: error
+            })
+        );
+    }
+
+    #[test]
+    fn enums() {
+        assert_eq!(Status::VALUES, ["active", "on hold", "it's closed"]);
+        assert_eq!(
+            SQLiteTextMode::Enum::to_sql_value(Status::OnHold),
+            Ok(SQLiteValue::Text("on hold".to_string()))
+        );
+        assert_eq!(
+            SQLiteTextMode::Enum::from_sql_value(SQLiteValue::Text("active".to_string())),
+            Ok(Status::Active)
+        );
+        assert!(
+            <SQLiteTextMode::Enum as SQLiteDataMode<Status>>::from_sql_value(SQLiteValue::Text(
+                "Active".to_string()
+            ))
+            .is_err()
+        );
+        assert_eq!(
+            text::<Status, _>("status", SQLiteTextMode::Enum)
+                .not_null()
+                .default(Status::Closed)
+                .check_values("status_values")
+                .to_sql()
+                .unwrap(),
+            concat!(
+                r#""status" TEXT NOT NULL DEFAULT 'it''s closed' "#,
+                r#"CONSTRAINT "status_values" CHECK ("status" IN ('active', 'on hold', 'it''s closed'))"#
+            )
+        );
+        assert_eq!(
+            text::<Separator, _>("separator", SQLiteTextMode::Enum)
+                .check_values("separator_values")
+                .to_sql(),
+            Err(SQLiteDefinitionError::Inline(InlineError::Literal(
+                SQLiteValue::Text("\0".to_string())
+            )))
+        );
+    }
+
+    #[test]
+    fn enum_columns() {
+        use common::expressions::conditions::{eq, is_not_null, or};
+
+        crate::sqlite_enum! {
+            #[derive(Debug, PartialEq)]
+            pub enum Role {
+                Admin = "admin",
+                Member = "member",
+            }
+        }
+
+        crate::sqlite_table!("accounts", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            role: text::<Role>("role", SQLiteTextMode::Enum).not_null().default(Role::Member).check_values("accounts_role"),
+            owner_id: integer("owner_id", SQLiteIntegerMode::Number),
+        }, |table| [
+            check("accounts_owner", or([eq(&table.role, Role::Admin), is_not_null(&table.owner_id)])),
+        ]);
+
+        let accounts = Accounts::new();
+
+        assert_eq!(
+            accounts.clone().to_sql().unwrap(),
+            concat!(
+                r#"CREATE TABLE "accounts" ("id" INTEGER PRIMARY KEY, "#,
+                r#""role" TEXT NOT NULL DEFAULT 'member' CONSTRAINT "accounts_role" CHECK ("role" IN ('admin', 'member')), "#,
+                r#""owner_id" INTEGER, "#,
+                r#"CONSTRAINT "accounts_owner" CHECK ((("role" = 'admin') OR ("owner_id" IS NOT NULL))))"#
+            )
+        );
+
+        let statement = insert(&accounts)
+            .values(InsertAccounts {
+                id: Some(1),
+                role: Some(Role::Admin),
+                owner_id: None,
+            })
+            .unwrap();
+
+        assert_eq!(
+            statement.params(),
+            [
+                SQLiteValue::Integer(1),
+                SQLiteValue::Text("admin".to_string())
+            ]
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&accounts.to_sql().unwrap(), ()).unwrap();
+        conn.execute(
+            &statement.clone().to_sql(),
+            rusqlite::params_from_iter(statement.params()),
+        )
+        .unwrap();
+        assert!(conn
+            .execute(r#"INSERT INTO "accounts" VALUES (2, 'owner', 1)"#, ())
+            .is_err());
+        assert!(conn
+            .execute(r#"INSERT INTO "accounts" ("id") VALUES (3)"#, ())
+            .is_err());
+
+        let role: String = conn
+            .query_row(r#"SELECT "role" FROM "accounts""#, (), |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(
+            SQLiteTextMode::Enum::from_sql_value(SQLiteValue::Text(role)),
+            Ok(Role::Admin)
+        );
+    }
+
+    #[test]
+    fn json_columns() {
+        use common::expressions::conditions::gt;
+
+        use crate::common::Integer;
+        use crate::expressions::json_value;
+
+        #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+        pub struct Profile {
+            age: i64,
+            city: String,
+        }
+
+        crate::sqlite_table!("profiles", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            data: text::<Profile>("data", SQLiteTextMode::JSON).not_null(),
+        }, |table| [
+            check("profiles_adult", gt(json_value::<Integer, _>(&table.data, "$.age"), 17)),
+        ]);
+
+        let profiles = Profiles::new();
+
+        assert_eq!(
+            profiles.clone().to_sql().unwrap(),
+            concat!(
+                r#"CREATE TABLE "profiles" ("id" INTEGER PRIMARY KEY, "data" TEXT NOT NULL, "#,
+                r#"CONSTRAINT "profiles_adult" CHECK (("data" ->> '$.age') > 17))"#
+            )
+        );
+
+        let profile = Profile {
+            age: 30,
+            city: "Oslo".to_string(),
+        };
+        let statement = insert(&profiles)
+            .values(InsertProfiles {
+                id: Some(1),
+                data: Some(profile.clone()),
+            })
+            .unwrap();
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&profiles.to_sql().unwrap(), ()).unwrap();
+        conn.execute(
+            &statement.clone().to_sql(),
+            rusqlite::params_from_iter(statement.params()),
+        )
+        .unwrap();
+        assert!(conn
+            .execute(
+                r#"INSERT INTO "profiles" VALUES (2, '{"age":12,"city":"Bergen"}')"#,
+                ()
+            )
+            .is_err());
+
+        let data: String = conn
+            .query_row(r#"SELECT "data" FROM "profiles""#, (), |row| row.get(0))
+            .unwrap();
+
+        assert_eq!(
+            <SQLiteTextMode::JSON as SQLiteDataMode<Profile>>::from_sql_value(SQLiteValue::Text(
+                data
+            )),
+            Ok(profile)
+        );
+    }
 }
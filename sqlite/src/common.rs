@@ -1,3 +1,54 @@
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{self, Display},
+    marker::PhantomData,
+};
+
+use common::{
+    expressions::conditions::CaseInsensitiveLike, Dialect, InlineError, SQLFragment, SQL,
+};
+
+use crate::traits::column::{SQLiteColumnType, SQLiteDataMode, SQLiteLiteral};
+
+/// SQLite's dialect: double-quoted identifiers and `?` placeholders.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SQLiteDialect;
+
+impl Dialect for SQLiteDialect {
+    type Param = SQLiteValue;
+
+    fn placeholder(_index: usize) -> String {
+        "?".to_string()
+    }
+
+    fn literal(value: &SQLiteValue) -> Option<String> {
+        value.to_literal()
+    }
+}
+
+// `LIKE` ignores collations and turns case-sensitive under `PRAGMA case_sensitive_like`, so both
+// sides are lowercased instead.
+impl CaseInsensitiveLike for SQLiteValue {
+    fn ilike(expression: SQL<Self>, pattern: SQL<Self>) -> SQL<Self, bool> {
+        lower(expression)
+            .push_str(" LIKE ")
+            .append(lower(pattern))
+            .cast()
+    }
+
+    fn not_ilike(expression: SQL<Self>, pattern: SQL<Self>) -> SQL<Self, bool> {
+        lower(expression)
+            .push_str(" NOT LIKE ")
+            .append(lower(pattern))
+            .cast()
+    }
+}
+
+fn lower(expression: SQL<SQLiteValue>) -> SQL<SQLiteValue> {
+    SQL::raw("lower(").append(expression).push_str(")")
+}
+
 pub type Integer = i64;
 pub type Real = f64;
 pub type Text = String;
@@ -28,3 +79,596 @@ impl Default for Number {
         Self::Int(0)
     }
 }
+
+/// A value bound to a statement parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SQLiteValue {
+    Null,
+    Integer(Integer),
+    Real(Real),
+    Text(Text),
+    Blob(Blob),
+}
+
+impl From<Integer> for SQLiteValue {
+    fn from(value: Integer) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<bool> for SQLiteValue {
+    fn from(value: bool) -> Self {
+        Self::Integer(value.into())
+    }
+}
+
+impl From<Real> for SQLiteValue {
+    fn from(value: Real) -> Self {
+        Self::Real(value)
+    }
+}
+
+impl From<Text> for SQLiteValue {
+    fn from(value: Text) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<Blob> for SQLiteValue {
+    fn from(value: Blob) -> Self {
+        Self::Blob(value)
+    }
+}
+
+impl From<Any> for SQLiteValue {
+    fn from(value: Any) -> Self {
+        match value {
+            Any::Integer(value) => Self::Integer(value),
+            Any::Real(value) => Self::Real(value),
+            Any::Text(value) => Self::Text(value),
+            Any::Blob(value) => Self::Blob(value),
+        }
+    }
+}
+
+impl From<Number> for SQLiteValue {
+    fn from(value: Number) -> Self {
+        match value {
+            Number::Int(value) => Self::Integer(value),
+            Number::Real(value) => Self::Real(value),
+        }
+    }
+}
+
+impl<T: Into<SQLiteValue>> From<Option<T>> for SQLiteValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+#[cfg(any(test, feature = "rusqlite"))]
+impl rusqlite::ToSql for SQLiteValue {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        use rusqlite::types::{ToSqlOutput, ValueRef};
+
+        Ok(ToSqlOutput::Borrowed(match self {
+            Self::Null => ValueRef::Null,
+            Self::Integer(value) => ValueRef::Integer(*value),
+            Self::Real(value) => ValueRef::Real(*value),
+            Self::Text(value) => ValueRef::Text(value.as_bytes()),
+            Self::Blob(value) => ValueRef::Blob(value),
+        }))
+    }
+}
+
+/// A stored value that cannot be read back as the Rust type of its column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SQLiteDecodeError {
+    /// What the column's mode expected, e.g. `"INTEGER 0 or 1"`.
+    pub expected: &'static str,
+    pub value: SQLiteValue,
+}
+
+impl SQLiteDecodeError {
+    pub fn new(expected: &'static str, value: SQLiteValue) -> Self {
+        Self { expected, value }
+    }
+}
+
+impl Display for SQLiteDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {}, found {:?}", self.expected, self.value)
+    }
+}
+
+impl Error for SQLiteDecodeError {}
+
+/// A Rust value that cannot be converted to what its column's mode stores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SQLiteEncodeError {
+    /// What the column's mode stores, e.g. `"JSON text"`.
+    pub expected: &'static str,
+    /// Why the value could not be converted.
+    pub reason: String,
+}
+
+impl SQLiteEncodeError {
+    pub fn new(expected: &'static str, reason: impl Display) -> Self {
+        Self {
+            expected,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl Display for SQLiteEncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot store value as {}: {}",
+            self.expected, self.reason
+        )
+    }
+}
+
+impl Error for SQLiteEncodeError {}
+
+/// A column, constraint, index or table whose DDL cannot be rendered.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SQLiteDefinitionError {
+    /// A value or placeholder that cannot be inlined, since SQLite binds no parameters in DDL.
+    Inline(InlineError<SQLiteValue>),
+    /// A column of another table where only columns of `table` are allowed.
+    OtherTable {
+        table: &'static str,
+        /// Table and name of the offending column.
+        column: (&'static str, &'static str),
+    },
+    /// The default of a column cannot be converted to what its mode stores.
+    Encode {
+        column: &'static str,
+        source: SQLiteEncodeError,
+    },
+}
+
+impl Display for SQLiteDefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Inline(source) => write!(f, "cannot render definition: {source}"),
+            Self::OtherTable {
+                table,
+                column: (other, column),
+            } => write!(
+                f,
+                r#"column "{other}"."{column}" does not belong to table "{table}""#
+            ),
+            Self::Encode { column, .. } => {
+                write!(f, r#"default of column "{column}" cannot be stored"#)
+            }
+        }
+    }
+}
+
+impl Error for SQLiteDefinitionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Inline(source) => Some(source),
+            Self::OtherTable { .. } => None,
+            Self::Encode { source, .. } => Some(source),
+        }
+    }
+}
+
+impl From<InlineError<SQLiteValue>> for SQLiteDefinitionError {
+    fn from(source: InlineError<SQLiteValue>) -> Self {
+        Self::Inline(source)
+    }
+}
+
+/// A SQL expression evaluated by SQLite to fill in a column default, typed by the storage class it
+/// produces (`Integer`, `Text`, ...) so it fits every mode stored that way.
+#[derive(Debug, Clone, Copy)]
+pub struct DefaultSQL<T> {
+    pub(crate) sql: &'static str,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> DefaultSQL<T> {
+    /// Wraps a raw SQL expression, e.g. `DefaultSQL::raw("lower(hex(randomblob(16)))")`.
+    pub const fn raw(sql: &'static str) -> Self {
+        Self {
+            sql,
+            _marker: PhantomData,
+        }
+    }
+}
+
+pub const CURRENT_TIME: DefaultSQL<Text> = DefaultSQL::raw("CURRENT_TIME");
+pub const CURRENT_DATE: DefaultSQL<Text> = DefaultSQL::raw("CURRENT_DATE");
+pub const CURRENT_TIMESTAMP: DefaultSQL<Text> = DefaultSQL::raw("CURRENT_TIMESTAMP");
+pub const UNIXEPOCH: DefaultSQL<Integer> = DefaultSQL::raw("unixepoch()");
+
+/// Conflict resolution algorithm of a `PRIMARY KEY`, `NOT NULL` or `UNIQUE` constraint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SQLiteConflict {
+    Rollback,
+    Abort,
+    Fail,
+    Ignore,
+    Replace,
+}
+
+impl Display for SQLiteConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Rollback => "ROLLBACK",
+            Self::Abort => "ABORT",
+            Self::Fail => "FAIL",
+            Self::Ignore => "IGNORE",
+            Self::Replace => "REPLACE",
+        })
+    }
+}
+
+/// Action taken on a referencing row when the row its foreign key points at is deleted or updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SQLiteReferenceAction {
+    Cascade,
+    SetNull,
+    SetDefault,
+    Restrict,
+    NoAction,
+}
+
+impl Display for SQLiteReferenceAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Cascade => "CASCADE",
+            Self::SetNull => "SET NULL",
+            Self::SetDefault => "SET DEFAULT",
+            Self::Restrict => "RESTRICT",
+            Self::NoAction => "NO ACTION",
+        })
+    }
+}
+
+/// Collating sequence used to compare text values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Collation {
+    Binary,
+    NoCase,
+    RTrim,
+    /// Collation registered on the connection, e.g. with [`SQLiteCustomCollation::register`].
+    Custom(&'static str),
+}
+
+impl Display for Collation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Binary => f.write_str("BINARY"),
+            Self::NoCase => f.write_str("NOCASE"),
+            Self::RTrim => f.write_str("RTRIM"),
+            Self::Custom(name) => f.write_str(&SQLiteDialect::quote_identifier(name)),
+        }
+    }
+}
+
+impl SQLFragment<SQLiteValue> for Collation {
+    fn into_fragment(self) -> SQL<SQLiteValue> {
+        match self {
+            Self::Custom(name) => SQL::name(name),
+            collation => SQL::raw(collation.to_string()),
+        }
+    }
+}
+
+/// A collation implemented in Rust, which must be registered on every connection using it.
+#[derive(Debug, Clone, Copy)]
+pub struct SQLiteCustomCollation {
+    name: &'static str,
+    compare: fn(&str, &str) -> Ordering,
+}
+
+impl SQLiteCustomCollation {
+    pub const fn new(name: &'static str, compare: fn(&str, &str) -> Ordering) -> Self {
+        Self { name, compare }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The comparison SQLite calls for this collation, for drivers other than rusqlite.
+    pub const fn compare(&self) -> fn(&str, &str) -> Ordering {
+        self.compare
+    }
+
+    pub const fn collation(&self) -> Collation {
+        Collation::Custom(self.name)
+    }
+
+    #[cfg(any(test, feature = "rusqlite"))]
+    pub fn register(&self, connection: &rusqlite::Connection) -> rusqlite::Result<()> {
+        connection.create_collation(self.name, self.compare)
+    }
+}
+
+impl From<SQLiteCustomCollation> for Collation {
+    fn from(value: SQLiteCustomCollation) -> Self {
+        value.collation()
+    }
+}
+
+impl SQLiteColumnType for Integer {
+    const SQL_TYPE: &'static str = "INTEGER";
+}
+
+impl SQLiteColumnType for Real {
+    const SQL_TYPE: &'static str = "REAL";
+}
+
+impl SQLiteColumnType for Text {
+    const SQL_TYPE: &'static str = "TEXT";
+}
+
+impl SQLiteColumnType for Blob {
+    const SQL_TYPE: &'static str = "BLOB";
+}
+
+impl SQLiteColumnType for Any {
+    const SQL_TYPE: &'static str = "ANY";
+}
+
+impl SQLiteColumnType for Number {
+    const SQL_TYPE: &'static str = "NUMERIC";
+}
+
+impl SQLiteLiteral for Integer {
+    fn to_literal(&self) -> Option<String> {
+        // `-9223372036854775808` would be parsed as a negated real, so spell it as an expression.
+        if *self == Integer::MIN {
+            return Some(format!("({} - 1)", Integer::MIN + 1));
+        }
+
+        Some(self.to_string())
+    }
+}
+
+impl SQLiteLiteral for Real {
+    fn to_literal(&self) -> Option<String> {
+        // `Debug` always keeps a fractional part or exponent, so SQLite reads the literal as REAL.
+        self.is_finite().then(|| format!("{self:?}"))
+    }
+}
+
+impl SQLiteLiteral for Text {
+    fn to_literal(&self) -> Option<String> {
+        if self.contains('\0') {
+            return None;
+        }
+
+        Some(format!("'{}'", self.replace('\'', "''")))
+    }
+}
+
+impl SQLiteLiteral for Blob {
+    fn to_literal(&self) -> Option<String> {
+        let hex: String = self.iter().map(|byte| format!("{byte:02X}")).collect();
+
+        Some(format!("X'{hex}'"))
+    }
+}
+
+impl SQLiteLiteral for Any {
+    fn to_literal(&self) -> Option<String> {
+        match self {
+            Self::Integer(value) => value.to_literal(),
+            Self::Real(value) => value.to_literal(),
+            Self::Text(value) => value.to_literal(),
+            Self::Blob(value) => value.to_literal(),
+        }
+    }
+}
+
+impl SQLiteLiteral for Number {
+    fn to_literal(&self) -> Option<String> {
+        match self {
+            Self::Int(value) => value.to_literal(),
+            Self::Real(value) => value.to_literal(),
+        }
+    }
+}
+
+impl SQLiteLiteral for bool {
+    fn to_literal(&self) -> Option<String> {
+        Some(Integer::from(*self).to_string())
+    }
+}
+
+impl SQLiteLiteral for SQLiteValue {
+    fn to_literal(&self) -> Option<String> {
+        match self {
+            Self::Null => Some("NULL".to_string()),
+            Self::Integer(value) => value.to_literal(),
+            Self::Real(value) => value.to_literal(),
+            Self::Text(value) => value.to_literal(),
+            Self::Blob(value) => value.to_literal(),
+        }
+    }
+}
+
+// Columns without modes store their Rust values as they are
+impl SQLiteDataMode<Real> for () {
+    fn to_sql_value(value: Real) -> Result<SQLiteValue, SQLiteEncodeError> {
+        Ok(SQLiteValue::Real(value))
+    }
+
+    fn from_sql_value(value: SQLiteValue) -> Result<Real, SQLiteDecodeError> {
+        match value {
+            SQLiteValue::Real(value) => Ok(value),
+            value => Err(SQLiteDecodeError::new("REAL", value)),
+        }
+    }
+}
+
+impl SQLiteDataMode<Any> for () {
+    fn to_sql_value(value: Any) -> Result<SQLiteValue, SQLiteEncodeError> {
+        Ok(value.into())
+    }
+
+    fn from_sql_value(value: SQLiteValue) -> Result<Any, SQLiteDecodeError> {
+        match value {
+            SQLiteValue::Integer(value) => Ok(Any::Integer(value)),
+            SQLiteValue::Real(value) => Ok(Any::Real(value)),
+            SQLiteValue::Text(value) => Ok(Any::Text(value)),
+            SQLiteValue::Blob(value) => Ok(Any::Blob(value)),
+            value => Err(SQLiteDecodeError::new("a value", value)),
+        }
+    }
+}
+
+impl SQLiteDataMode<Number> for () {
+    fn to_sql_value(value: Number) -> Result<SQLiteValue, SQLiteEncodeError> {
+        Ok(value.into())
+    }
+
+    fn from_sql_value(value: SQLiteValue) -> Result<Number, SQLiteDecodeError> {
+        match value {
+            SQLiteValue::Integer(value) => Ok(Number::Int(value)),
+            SQLiteValue::Real(value) => Ok(Number::Real(value)),
+            value => Err(SQLiteDecodeError::new("INTEGER or REAL", value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::SQLFragment;
+
+    use crate::{prelude::*, traits::column::SQLiteLiteral};
+
+    use super::{Any, Collation, Number, SQLiteDialect, SQLiteValue};
+
+    crate::sqlite_table!("users_table", {
+        id: integer("id", SQLiteIntegerMode::Number).primary(),
+        name: text("name", SQLiteTextMode::String).not_null(),
+        score: real("score"),
+    });
+
+    #[test]
+    fn literals() {
+        assert_eq!(42.to_literal().as_deref(), Some("42"));
+        assert_eq!((-42).to_literal().as_deref(), Some("-42"));
+        assert_eq!(
+            i64::MIN.to_literal().as_deref(),
+            Some("(-9223372036854775807 - 1)")
+        );
+
+        assert_eq!(1.0.to_literal().as_deref(), Some("1.0"));
+        assert_eq!((-0.5).to_literal().as_deref(), Some("-0.5"));
+        assert_eq!(1e300.to_literal().as_deref(), Some("1e300"));
+        assert_eq!(f64::NAN.to_literal(), None);
+        assert_eq!(f64::INFINITY.to_literal(), None);
+        assert_eq!(f64::NEG_INFINITY.to_literal(), None);
+
+        assert_eq!("it's".to_string().to_literal().as_deref(), Some("'it''s'"));
+        assert_eq!("a\0b".to_string().to_literal(), None);
+
+        assert_eq!(
+            vec![0x00, 0xAB, 0x10].to_literal().as_deref(),
+            Some("X'00AB10'")
+        );
+        assert_eq!(Vec::<u8>::new().to_literal().as_deref(), Some("X''"));
+
+        assert_eq!(Any::Text("x".into()).to_literal().as_deref(), Some("'x'"));
+        assert_eq!(Any::Real(f64::NAN).to_literal(), None);
+        assert_eq!(Number::Int(7).to_literal().as_deref(), Some("7"));
+        assert_eq!(Number::Real(7.5).to_literal().as_deref(), Some("7.5"));
+    }
+
+    #[test]
+    fn collations() {
+        let custom = Collation::Custom(r#"by "length""#);
+
+        assert_eq!(Collation::NoCase.to_string(), "NOCASE");
+        assert_eq!(custom.to_string(), r#""by ""length""""#);
+        assert_eq!(
+            custom.into_fragment().build_inline::<SQLiteDialect>(),
+            Ok(r#""by ""length""""#.to_string())
+        );
+    }
+
+    #[test]
+    fn sql_macro() {
+        let users = UsersTable::new();
+        let name = "x' OR '1' = '1";
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&users.clone().to_sql().unwrap(), ()).unwrap();
+        conn.execute(
+            r#"INSERT INTO "users_table" ("id", "name") VALUES (1, 'ann'), (2, 'bob')"#,
+            (),
+        )
+        .unwrap();
+
+        let count = |query: SQL<SQLiteValue, i64>| -> i64 {
+            let (sql, params) = query.build::<SQLiteDialect>();
+
+            conn.query_row(&sql, rusqlite::params_from_iter(params), |row| row.get(0))
+                .unwrap()
+        };
+
+        assert_eq!(
+            count(sql!(<i64> "SELECT count(*) FROM {users} WHERE {users.name} = {name}")),
+            0
+        );
+        assert_eq!(
+            count(sql!(<i64> "SELECT count(*) FROM {} WHERE {} = {}", &users, &users.name, "bob")),
+            1
+        );
+    }
+
+    #[test]
+    fn custom_collations() {
+        use std::cmp::Ordering;
+
+        use common::expressions::conditions::eq;
+
+        use crate::expressions::collate;
+
+        const BY_LENGTH: SQLiteCustomCollation =
+            SQLiteCustomCollation::new("by_length", |a: &str, b: &str| -> Ordering {
+                a.len().cmp(&b.len())
+            });
+
+        crate::sqlite_table!("tags", {
+            name: text("name", SQLiteTextMode::String).collate(Collation::NoCase).unique("tags_name_unique"),
+            code: text("code", SQLiteTextMode::String).collate(BY_LENGTH),
+        }, |table| [
+            check("tags_code_length", eq(collate(&table.code, BY_LENGTH), "abc")),
+        ]);
+
+        let tags = Tags::new().to_sql().unwrap();
+
+        assert_eq!(
+            tags,
+            concat!(
+                r#"CREATE TABLE "tags" ("name" TEXT COLLATE NOCASE CONSTRAINT "tags_name_unique" UNIQUE, "#,
+                r#""code" TEXT COLLATE "by_length", "#,
+                r#"CONSTRAINT "tags_code_length" CHECK (("code" COLLATE "by_length") = 'abc'))"#
+            )
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        BY_LENGTH.register(&conn).unwrap();
+        conn.execute(&tags, ()).unwrap();
+        conn.execute(r#"INSERT INTO "tags" VALUES ('Rust', 'xyz')"#, ())
+            .unwrap();
+        assert!(conn
+            .execute(r#"INSERT INTO "tags" VALUES ('rust', 'xyz')"#, ())
+            .is_err());
+        assert!(conn
+            .execute(r#"INSERT INTO "tags" VALUES ('go', 'abcd')"#, ())
+            .is_err());
+    }
+}
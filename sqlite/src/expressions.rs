@@ -0,0 +1,548 @@
+use common::{expressions::Expression, traits::Comparable, SQLFragment, SQL};
+
+use crate::{
+    columns::blob::SQLiteBigInt,
+    common::{Collation, SQLiteEncodeError, SQLiteValue, Text},
+    traits::column::SQLiteDataMode,
+};
+
+/// Operand holding JSON: a column in a JSON mode or the result of [`json_field`].
+pub trait SQLiteJSONExpression: Expression<SQLiteValue> {}
+
+impl SQLiteJSONExpression for SQL<SQLiteValue, serde_json::Value> {}
+
+/// A `u128` or `i128` for comparing with `SQLiteBlobMode::BigInt` columns, bound as the blob the
+/// column stores, e.g. `gt(&table.balance, big_int(100i128))`.
+pub fn big_int<T: SQLiteBigInt>(value: T) -> SQL<SQLiteValue, T> {
+    SQL::param(SQLiteValue::Blob(value.to_bytes().to_vec())).cast()
+}
+
+/// `value` bound as a column in `mode` stores it, for comparing with such a column, e.g.
+/// `gt(&events.created_at, encoded(SQLiteIntegerMode::Timestamp, SystemTime::now())?)`.
+pub fn encoded<T: Clone, M: SQLiteDataMode<T>>(
+    _mode: M,
+    value: T,
+) -> Result<SQL<SQLiteValue, T>, SQLiteEncodeError> {
+    Ok(M::write_sql(M::to_sql_value(value)?).cast())
+}
+
+fn arithmetic<L, R>(left: L, operator: &'static str, right: R) -> SQL<SQLiteValue, L::DataType>
+where
+    L: Expression<SQLiteValue>,
+    R: Expression<SQLiteValue>,
+    L::DataType: Comparable<R::DataType>,
+{
+    left.into_sql().push_str(operator).append(right.into_sql())
+}
+
+pub fn add<L, R>(left: L, right: R) -> SQL<SQLiteValue, L::DataType>
+where
+    L: Expression<SQLiteValue>,
+    R: Expression<SQLiteValue>,
+    L::DataType: Comparable<R::DataType>,
+{
+    arithmetic(left, " + ", right)
+}
+
+pub fn sub<L, R>(left: L, right: R) -> SQL<SQLiteValue, L::DataType>
+where
+    L: Expression<SQLiteValue>,
+    R: Expression<SQLiteValue>,
+    L::DataType: Comparable<R::DataType>,
+{
+    arithmetic(left, " - ", right)
+}
+
+pub fn mul<L, R>(left: L, right: R) -> SQL<SQLiteValue, L::DataType>
+where
+    L: Expression<SQLiteValue>,
+    R: Expression<SQLiteValue>,
+    L::DataType: Comparable<R::DataType>,
+{
+    arithmetic(left, " * ", right)
+}
+
+pub fn div<L, R>(left: L, right: R) -> SQL<SQLiteValue, L::DataType>
+where
+    L: Expression<SQLiteValue>,
+    R: Expression<SQLiteValue>,
+    L::DataType: Comparable<R::DataType>,
+{
+    arithmetic(left, " / ", right)
+}
+
+pub fn concat<L, R>(left: L, right: R) -> SQL<SQLiteValue, Text>
+where
+    L: Expression<SQLiteValue, DataType = Text>,
+    R: Expression<SQLiteValue>,
+    Text: Comparable<R::DataType>,
+{
+    arithmetic(left, " || ", right)
+}
+
+/// Applies `collation` to `expression`, so comparisons and sorting on it use that collation.
+pub fn collate<E: Expression<SQLiteValue>>(
+    expression: E,
+    collation: impl Into<Collation>,
+) -> SQL<SQLiteValue, E::DataType> {
+    expression
+        .into_sql()
+        .push_str(" COLLATE ")
+        .append(collation.into().into_fragment())
+}
+
+/// `json_extract(json, path)`: the value at `path`, read as the SQL value `T`.
+pub fn json_extract<T, E: SQLiteJSONExpression>(json: E, path: &str) -> SQL<SQLiteValue, T> {
+    SQL::raw("json_extract(")
+        .append(json.into_sql())
+        .push_str(", ")
+        .append(path.into_sql())
+        .push_str(")")
+        .cast()
+}
+
+/// `json -> path`: the JSON of the value at `path`, which can be queried further.
+pub fn json_field<E: SQLiteJSONExpression>(
+    json: E,
+    path: &str,
+) -> SQL<SQLiteValue, serde_json::Value> {
+    json.into_sql()
+        .cast()
+        .push_str(" -> ")
+        .append(path.into_sql())
+}
+
+/// `json ->> path`: the value at `path`, read as the SQL value `T`.
+pub fn json_value<T, E: SQLiteJSONExpression>(json: E, path: &str) -> SQL<SQLiteValue, T> {
+    json.into_sql()
+        .cast()
+        .push_str(" ->> ")
+        .append(path.into_sql())
+}
+
+#[cfg(test)]
+mod test {
+    use common::{
+        expressions::conditions::{eq, gt},
+        traits::ColumnBuilder,
+    };
+
+    use crate::{
+        columns::{
+            integer::{integer, SQLiteIntegerMode},
+            text::{text, SQLiteTextMode},
+        },
+        common::{Collation, Integer, SQLiteDialect, SQLiteValue, Text},
+        prelude::*,
+    };
+
+    use super::{collate, concat, encoded, json_extract, json_field, json_value, mul, sub};
+
+    crate::sqlite_table!("users_table", {
+        id: integer("id", SQLiteIntegerMode::Number).primary(),
+        name: text("name", SQLiteTextMode::String).not_null(),
+        score: real("score"),
+        avatar: blob("avatar", SQLiteBlobMode::Buffer),
+    });
+
+    #[test]
+    fn arithmetic() {
+        let age = integer("age", SQLiteIntegerMode::Number).in_table("users");
+        let name = text("name", SQLiteTextMode::String).in_table("users");
+
+        assert_eq!(
+            mul(sub(&age, 1), 12).build::<SQLiteDialect>(),
+            (
+                r#"("users"."age" - ?) * ?"#.to_string(),
+                vec![SQLiteValue::Integer(1), SQLiteValue::Integer(12)]
+            )
+        );
+        assert_eq!(
+            concat(&name, "!").build_inline::<SQLiteDialect>(),
+            Ok(r#""name" || '!'"#.to_string())
+        );
+        assert_eq!(
+            eq(collate(&name, Collation::NoCase), "bob").build_inline::<SQLiteDialect>(),
+            Ok(r#"("name" COLLATE NOCASE) = 'bob'"#.to_string())
+        );
+    }
+
+    #[test]
+    fn json() {
+        let meta = text::<serde_json::Value, _>("meta", SQLiteTextMode::JSON).in_table("users");
+
+        assert_eq!(
+            gt(json_extract::<Integer, _>(&meta, "$.age"), 17).build::<SQLiteDialect>(),
+            (
+                r#"(json_extract("users"."meta", ?)) > ?"#.to_string(),
+                vec![
+                    SQLiteValue::Text("$.age".to_string()),
+                    SQLiteValue::Integer(17)
+                ]
+            )
+        );
+        assert_eq!(
+            eq(
+                json_value::<Text, _>(json_field(&meta, "$.address"), "$.city"),
+                "Oslo"
+            )
+            .build_inline::<SQLiteDialect>(),
+            Ok(r#"(("meta" -> '$.address') ->> '$.city') = 'Oslo'"#.to_string())
+        );
+    }
+
+    #[test]
+    fn encoded_values() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        use common::expressions::conditions::lte;
+
+        crate::sqlite_table!("events", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            created_at: integer("created_at", SQLiteIntegerMode::Timestamp).not_null(),
+            seen_at: integer("seen_at", SQLiteIntegerMode::TimestampMS),
+        });
+
+        let events = Events::new();
+        let since = UNIX_EPOCH + Duration::from_millis(1_500);
+
+        assert_eq!(
+            gt(
+                &events.created_at,
+                encoded(SQLiteIntegerMode::Timestamp, since).unwrap()
+            )
+            .build::<SQLiteDialect>(),
+            (
+                r#""events"."created_at" > (?)"#.to_string(),
+                vec![SQLiteValue::Integer(1)]
+            )
+        );
+        assert_eq!(
+            lte(
+                &events.seen_at,
+                encoded(SQLiteIntegerMode::TimestampMS, since).unwrap()
+            )
+            .build::<SQLiteDialect>()
+            .1,
+            [SQLiteValue::Integer(1_500)]
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&events.clone().to_sql().unwrap(), ()).unwrap();
+        conn.execute(
+            r#"INSERT INTO "events" VALUES (1, 0, 0), (2, 1, 1500), (3, 5, 5000)"#,
+            (),
+        )
+        .unwrap();
+
+        let ids = |condition: SQL<SQLiteValue, bool>| -> Vec<i64> {
+            let (sql, params) = condition.build::<SQLiteDialect>();
+            let mut statement = conn
+                .prepare(&format!(
+                    r#"SELECT "id" FROM "events" WHERE {sql} ORDER BY "id""#
+                ))
+                .unwrap();
+            statement
+                .query_map(rusqlite::params_from_iter(params), |row| row.get(0))
+                .unwrap()
+                .map(Result::unwrap)
+                .collect()
+        };
+
+        assert_eq!(
+            ids(gt(
+                &events.created_at,
+                encoded(SQLiteIntegerMode::Timestamp, since).unwrap()
+            )),
+            vec![3]
+        );
+        assert_eq!(
+            ids(lte(
+                &events.seen_at,
+                encoded(SQLiteIntegerMode::TimestampMS, since).unwrap()
+            )),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn conditions() {
+        use common::expressions::conditions::{
+            between, eq, exists, gt, in_array, is_null, like, ne, not_in_array, not_like,
+        };
+
+        fn build<T>(sql: SQL<SQLiteValue, T>) -> (String, Vec<SQLiteValue>) {
+            sql.build::<SQLiteDialect>()
+        }
+
+        let users = UsersTable::new();
+
+        assert_eq!(
+            build(eq(&users.id, 42)),
+            (
+                r#""users_table"."id" = ?"#.to_string(),
+                vec![SQLiteValue::Integer(42)]
+            )
+        );
+        assert_eq!(
+            build(eq(eq(&users.name, "bob"), false)),
+            (
+                r#"("users_table"."name" = ?) = ?"#.to_string(),
+                vec![
+                    SQLiteValue::Text("bob".to_string()),
+                    SQLiteValue::Integer(0)
+                ]
+            )
+        );
+        assert_eq!(
+            build(between(&users.score, 1.0, 2.5)),
+            (
+                r#""users_table"."score" BETWEEN ? AND ?"#.to_string(),
+                vec![SQLiteValue::Real(1.0), SQLiteValue::Real(2.5)]
+            )
+        );
+        assert_eq!(
+            build(in_array(&users.id, [1, 2, 3])).0,
+            r#""users_table"."id" IN (?, ?, ?)"#
+        );
+        assert_eq!(build(in_array(&users.id, Vec::<i64>::new())).0, "1 = 0");
+        assert_eq!(build(not_in_array(&users.id, Vec::<i64>::new())).0, "1 = 1");
+        assert_eq!(
+            build(like(&users.name, "b%")).0,
+            r#""users_table"."name" LIKE ?"#
+        );
+        assert_eq!(
+            build(not_like(&users.name, "b%")).0,
+            r#""users_table"."name" NOT LIKE ?"#
+        );
+        assert_eq!(
+            build(is_null(&users.avatar)).0,
+            r#""users_table"."avatar" IS NULL"#
+        );
+        assert_eq!(
+            build(ne(&users.score, &users.score)).0,
+            r#""users_table"."score" <> "users_table"."score""#
+        );
+
+        let subquery = SQL::raw("SELECT 1 FROM ")
+            .append(SQL::table("users_table"))
+            .push_str(" WHERE ")
+            .append(gt(&users.id, 5));
+        assert_eq!(
+            build(exists(subquery)),
+            (
+                r#"EXISTS (SELECT 1 FROM "users_table" WHERE "users_table"."id" > ?)"#.to_string(),
+                vec![SQLiteValue::Integer(5)]
+            )
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&users.clone().to_sql().unwrap(), ()).unwrap();
+        conn.execute(
+            r#"INSERT INTO "users_table" ("id", "name") VALUES (1, 'ann'), (2, 'bob')"#,
+            (),
+        )
+        .unwrap();
+        let (sql, params) = build(gt(&users.id, 1));
+        let name: String = conn
+            .query_row(
+                &format!(r#"SELECT "name" FROM "users_table" WHERE {sql}"#),
+                rusqlite::params_from_iter(params),
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(name, "bob");
+    }
+
+    #[test]
+    fn combinators() {
+        use common::expressions::conditions::{and, eq, gt, is_null, like, lt, not, or};
+
+        fn build<T>(sql: SQL<SQLiteValue, T>) -> (String, Vec<SQLiteValue>) {
+            sql.build::<SQLiteDialect>()
+        }
+
+        let users = UsersTable::new();
+
+        assert_eq!(
+            build(and([gt(&users.id, 1), like(&users.name, "b%")])),
+            (
+                r#"(("users_table"."id" > ?) AND ("users_table"."name" LIKE ?))"#.to_string(),
+                vec![SQLiteValue::Integer(1), SQLiteValue::Text("b%".to_string())]
+            )
+        );
+        assert_eq!(
+            build(and([
+                and([gt(&users.id, 1), lt(&users.id, 9)]),
+                or([
+                    is_null(&users.avatar),
+                    or([eq(&users.id, 2), eq(&users.id, 3)])
+                ]),
+            ]))
+            .0,
+            concat!(
+                r#"(("users_table"."id" > ?) AND ("users_table"."id" < ?) AND "#,
+                r#"(("users_table"."avatar" IS NULL) OR ("users_table"."id" = ?) OR ("users_table"."id" = ?)))"#
+            )
+        );
+        assert_eq!(
+            build(or([None, Some(eq(&users.id, 2)), None])).0,
+            r#""users_table"."id" = ?"#
+        );
+        assert_eq!(build(and(Vec::<SQL<SQLiteValue, bool>>::new())).0, "1 = 1");
+        assert_eq!(build(or([None::<SQL<SQLiteValue, bool>>])).0, "1 = 0");
+        assert_eq!(
+            build(not(eq(&users.id, 2))).0,
+            r#"NOT ("users_table"."id" = ?)"#
+        );
+        assert_eq!(
+            build(not(or([eq(&users.id, 2), eq(&users.id, 3)]))).0,
+            r#"NOT (("users_table"."id" = ?) OR ("users_table"."id" = ?))"#
+        );
+
+        // a raw fragment keeps its meaning whatever it is combined with
+        let either = sql!(<bool> "{} = 1 OR {} = 2", &users.id, &users.id);
+        assert_eq!(
+            build(and([either.clone(), eq(&users.name, "bob")])).0,
+            r#"(("users_table"."id" = 1 OR "users_table"."id" = 2) AND ("users_table"."name" = ?))"#
+        );
+        assert_eq!(
+            build(not(either.clone())).0,
+            r#"NOT ("users_table"."id" = 1 OR "users_table"."id" = 2)"#
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&users.clone().to_sql().unwrap(), ()).unwrap();
+        conn.execute(
+            r#"INSERT INTO "users_table" ("id", "name") VALUES (1, 'ann'), (2, 'bob'), (3, 'bea')"#,
+            (),
+        )
+        .unwrap();
+
+        let count = |min_id: Option<i64>, prefix: Option<&str>| -> i64 {
+            let filter = and([
+                min_id.map(|id| gt(&users.id, id)),
+                prefix.map(|prefix| like(&users.name, prefix)),
+                Some(not(eq(&users.name, "zed"))),
+            ]);
+            let (sql, params) = build(filter);
+
+            conn.query_row(
+                &format!(r#"SELECT count(*) FROM "users_table" WHERE {sql}"#),
+                rusqlite::params_from_iter(params),
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(count(None, None), 3);
+        assert_eq!(count(Some(1), None), 2);
+        assert_eq!(count(Some(1), Some("b%")), 2);
+        assert_eq!(count(None, Some("a%")), 1);
+
+        let count = |filter: SQL<SQLiteValue, bool>| -> i64 {
+            let (sql, params) = build(filter);
+
+            conn.query_row(
+                &format!(r#"SELECT count(*) FROM "users_table" WHERE {sql}"#),
+                rusqlite::params_from_iter(params),
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(count(and([either.clone(), eq(&users.name, "bob")])), 1);
+        assert_eq!(count(not(either)), 1);
+    }
+
+    #[test]
+    fn dialect_operators() {
+        use common::expressions::conditions::{
+            array_contained, array_contains, array_overlaps, ilike, not_ilike,
+        };
+
+        crate::sqlite_table!("posts", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            title: text("title", SQLiteTextMode::String).not_null(),
+            tags: text::<Vec<String>>("tags", SQLiteTextMode::JSON),
+            scores: blob::<Vec<i64>>("scores", SQLiteBlobMode::JSON),
+        });
+
+        let posts = Posts::new();
+
+        assert_eq!(
+            ilike(&posts.title, "rust%").build::<SQLiteDialect>(),
+            (
+                r#"lower("posts"."title") LIKE lower(?)"#.to_string(),
+                vec![SQLiteValue::Text("rust%".to_string())]
+            )
+        );
+        assert_eq!(
+            array_contains(&posts.tags, ["a", "b"]).build::<SQLiteDialect>(),
+            (
+                concat!(
+                    "NOT EXISTS (SELECT value FROM json_each(json_array(?, ?)) ",
+                    r#"WHERE value NOT IN (SELECT value FROM json_each("posts"."tags")))"#
+                )
+                .to_string(),
+                vec![
+                    SQLiteValue::Text("a".to_string()),
+                    SQLiteValue::Text("b".to_string())
+                ]
+            )
+        );
+        assert_eq!(
+            array_overlaps(&posts.scores, [1, 2])
+                .build::<SQLiteDialect>()
+                .0,
+            r#"EXISTS (SELECT value FROM json_each("posts"."scores") WHERE value IN (?, ?))"#
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&posts.clone().to_sql().unwrap(), ()).unwrap();
+        conn.execute_batch(
+            r#"
+            PRAGMA case_sensitive_like = ON;
+            INSERT INTO "posts" ("id", "title", "tags", "scores") VALUES
+                (1, 'Rust Tips', '["a", "b", "c"]', jsonb('[1, 2]')),
+                (2, 'rusty nails', '["b"]', jsonb('[3]')),
+                (3, 'Go Tips', '[]', jsonb('[]'));
+            "#,
+        )
+        .unwrap();
+
+        let ids = |condition: SQL<SQLiteValue, bool>| -> Vec<i64> {
+            let (sql, params) = condition.build::<SQLiteDialect>();
+
+            let mut statement = conn
+                .prepare(&format!(
+                    r#"SELECT "id" FROM "posts" WHERE {sql} ORDER BY "id""#
+                ))
+                .unwrap();
+            statement
+                .query_map(rusqlite::params_from_iter(params), |row| row.get(0))
+                .unwrap()
+                .map(Result::unwrap)
+                .collect()
+        };
+
+        assert_eq!(ids(ilike(&posts.title, "RUST%")), vec![1, 2]);
+        assert_eq!(ids(not_ilike(&posts.title, "%tips")), vec![2]);
+        assert_eq!(ids(array_contains(&posts.tags, ["b", "a"])), vec![1]);
+        assert_eq!(ids(array_contains(&posts.tags, ["b"])), vec![1, 2]);
+        assert_eq!(
+            ids(array_contains(&posts.tags, Vec::<&str>::new())),
+            vec![1, 2, 3]
+        );
+        assert_eq!(ids(array_contained(&posts.tags, ["b", "z"])), vec![2, 3]);
+        assert_eq!(
+            ids(array_contained(&posts.scores, [1, 2, 3])),
+            vec![1, 2, 3]
+        );
+        assert_eq!(ids(array_overlaps(&posts.scores, [2, 3])), vec![1, 2]);
+        assert_eq!(
+            ids(array_overlaps(&posts.tags, Vec::<&str>::new())),
+            Vec::<i64>::new()
+        );
+    }
+}
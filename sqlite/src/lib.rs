@@ -1,243 +1,638 @@
-mod columns;
-mod common;
-mod table;
-mod traits;
+pub mod columns;
+pub mod common;
+pub mod expressions;
+pub mod query;
+pub mod table;
+pub mod traits;
+
+#[doc(hidden)]
+pub use paste;
+
+pub mod prelude {
+    pub use ::common::{expressions::Expression, sql, traits::*, SQLFragment, ToSQL, SQL};
+
+    pub use crate::columns::{
+        any::any,
+        blob::{blob, SQLiteBlobMode},
+        custom_type::{custom_type, SQLiteCustomType},
+        integer::{integer, SQLiteIntegerMode},
+        number::number,
+        real::real,
+        text::{text, SQLiteEnum, SQLiteTextMode},
+    };
+    pub use crate::common::{
+        Collation, DefaultSQL, SQLiteConflict, SQLiteCustomCollation, SQLiteDialect,
+        SQLiteReferenceAction, CURRENT_DATE, CURRENT_TIME, CURRENT_TIMESTAMP, UNIXEPOCH,
+    };
+    pub use crate::query::{insert::insert, update::update};
+    pub use crate::table::{
+        constraints::{check, foreign_key, primary_key, unique},
+        index::{index, indexed, unique_index},
+    };
+    pub use crate::traits::{column::*, table::*};
+}
 
 // Main macro to construct the SQLiteColumn type with the appropriate generics
+#[doc(hidden)]
+#[macro_export]
 macro_rules! sqlite_column_type {
-    (integer $(.$func:ident)*) => {
-        SQLiteIntegerColumnBuilder<
-            detect_primary_key!($(.$func)*),
-            detect_not_null!($(.$func)*),
-            detect_unique!($(.$func)*),
-            detect_autoincrement!($(.$func)*),
-            detect_default!($(.$func)*),
-            detect_default_fn!($(.$func)*),
+    (integer [] ($name:expr, SQLiteIntegerMode::$mode:ident) $(.$func:ident)*) => {
+        $crate::sqlite_column_type!(integer [] ($name, $crate::columns::integer::SQLiteIntegerMode::$mode) $(.$func)*)
+    };
+    (integer [] ($name:expr, $mode:ty) $(.$func:ident)*) => {
+        $crate::columns::integer::SQLiteIntegerColumn<
+            $mode,
+            $crate::detect_primary_key!($(.$func)*),
+            $crate::detect_not_null!($(.$func)*),
+            $crate::detect_unique!($(.$func)*),
+            $crate::detect_autoincrement!($(.$func)*),
+            $crate::detect_default!($(.$func)*),
+            $crate::detect_default_fn!($(.$func)*),
+            fn() -> Result<<$mode as $crate::columns::integer::IntegerMode>::DataType, ::std::fmt::Error>,
+            $crate::detect_update_fn!($(.$func)*),
+            fn() -> Result<<$mode as $crate::columns::integer::IntegerMode>::DataType, ::std::fmt::Error>,
+            $crate::detect_references!($(.$func)*),
+            $crate::detect_generated!($(.$func)*),
+        >
+    };
+    (real [] ($($args:tt)*) $(.$func:ident)*) => {
+        $crate::columns::real::SQLiteRealColumn<
+            $crate::detect_primary_key!($(.$func)*),
+            $crate::detect_not_null!($(.$func)*),
+            $crate::detect_unique!($(.$func)*),
+            $crate::detect_default!($(.$func)*),
+            $crate::detect_default_fn!($(.$func)*),
+            fn() -> Result<f64, ::std::fmt::Error>,
+            $crate::detect_update_fn!($(.$func)*),
+            fn() -> Result<f64, ::std::fmt::Error>,
+            $crate::detect_references!($(.$func)*),
+            $crate::detect_generated!($(.$func)*),
+        >
+    };
+    (text [$($data_type:ty)?] ($name:expr, SQLiteTextMode::$mode:ident) $(.$func:ident)*) => {
+        $crate::sqlite_column_type!(text [$($data_type)?] ($name, $crate::columns::text::SQLiteTextMode::$mode) $(.$func)*)
+    };
+    (text [] ($name:expr, $mode:ty) $(.$func:ident)*) => {
+        $crate::sqlite_column_type!(text [<$mode as $crate::columns::text::TextMode>::DataType] ($name, $mode) $(.$func)*)
+    };
+    (text [$data_type:ty] ($name:expr, $mode:ty) $(.$func:ident)*) => {
+        $crate::columns::text::SQLiteTextColumn<
+            $mode,
+            $data_type,
+            $crate::detect_primary_key!($(.$func)*),
+            $crate::detect_not_null!($(.$func)*),
+            $crate::detect_unique!($(.$func)*),
+            $crate::detect_default!($(.$func)*),
+            $crate::detect_default_fn!($(.$func)*),
+            fn() -> Result<$data_type, ::std::fmt::Error>,
+            $crate::detect_update_fn!($(.$func)*),
+            fn() -> Result<$data_type, ::std::fmt::Error>,
+            $crate::detect_references!($(.$func)*),
+            $crate::detect_generated!($(.$func)*),
         >
     };
-    (real $(.$func:ident)*) => {
-        SQLiteRealColumnBuilder<
-            detect_primary_key!($(.$func)*),
-            detect_not_null!($(.$func)*),
-            detect_unique!($(.$func)*),
-            detect_default!($(.$func)*),
-            detect_default_fn!($(.$func)*),
+    (blob [$($data_type:ty)?] ($name:expr, SQLiteBlobMode::$mode:ident) $(.$func:ident)*) => {
+        $crate::sqlite_column_type!(blob [$($data_type)?] ($name, $crate::columns::blob::SQLiteBlobMode::$mode) $(.$func)*)
+    };
+    (blob [] ($name:expr, $mode:ty) $(.$func:ident)*) => {
+        $crate::sqlite_column_type!(blob [<$mode as $crate::columns::blob::BlobMode>::DataType] ($name, $mode) $(.$func)*)
+    };
+    (blob [$data_type:ty] ($name:expr, $mode:ty) $(.$func:ident)*) => {
+        $crate::columns::blob::SQLiteBlobColumn<
+            $mode,
+            $data_type,
+            $crate::detect_primary_key!($(.$func)*),
+            $crate::detect_not_null!($(.$func)*),
+            $crate::detect_unique!($(.$func)*),
+            $crate::detect_default!($(.$func)*),
+            $crate::detect_default_fn!($(.$func)*),
+            fn() -> Result<$data_type, ::std::fmt::Error>,
+            $crate::detect_update_fn!($(.$func)*),
+            fn() -> Result<$data_type, ::std::fmt::Error>,
+            $crate::detect_references!($(.$func)*),
+            $crate::detect_generated!($(.$func)*),
         >
     };
-    (text $(.$func:ident)*) => {
-        SQLiteTextColumnBuilder<
-            detect_primary_key!($(.$func)*),
-            detect_not_null!($(.$func)*),
-            detect_unique!($(.$func)*),
-            detect_default!($(.$func)*),
-            detect_default_fn!($(.$func)*),
+    (custom_type [] ($name:expr, $custom_type:ty) $(.$func:ident)*) => {
+        $crate::columns::custom_type::SQLiteCustomColumn<
+            $custom_type,
+            $crate::detect_primary_key!($(.$func)*),
+            $crate::detect_not_null!($(.$func)*),
+            $crate::detect_unique!($(.$func)*),
+            $crate::detect_default!($(.$func)*),
+            $crate::detect_default_fn!($(.$func)*),
+            fn() -> Result<<$custom_type as $crate::columns::custom_type::SQLiteCustomType>::DataType, ::std::fmt::Error>,
+            $crate::detect_update_fn!($(.$func)*),
+            fn() -> Result<<$custom_type as $crate::columns::custom_type::SQLiteCustomType>::DataType, ::std::fmt::Error>,
+            $crate::detect_references!($(.$func)*),
+            $crate::detect_generated!($(.$func)*),
         >
     };
-    (blob $(.$func:ident)*) => {
-        SQLiteBlobColumnBuilder<
-            detect_primary_key!($(.$func)*),
-            detect_not_null!($(.$func)*),
-            detect_unique!($(.$func)*),
-            detect_default!($(.$func)*),
-            detect_default_fn!($(.$func)*),
+    (any [] ($($args:tt)*) $(.$func:ident)*) => {
+        $crate::columns::any::SQLiteAnyColumn<
+            $crate::detect_primary_key!($(.$func)*),
+            $crate::detect_not_null!($(.$func)*),
+            $crate::detect_unique!($(.$func)*),
+            $crate::detect_default!($(.$func)*),
+            $crate::detect_default_fn!($(.$func)*),
+            fn() -> Result<$crate::common::Any, ::std::fmt::Error>,
+            $crate::detect_update_fn!($(.$func)*),
+            fn() -> Result<$crate::common::Any, ::std::fmt::Error>,
+            $crate::detect_references!($(.$func)*),
+            $crate::detect_generated!($(.$func)*),
+        >
+    };
+    (number [] ($($args:tt)*) $(.$func:ident)*) => {
+        $crate::columns::number::SQLiteNumberColumn<
+            $crate::detect_primary_key!($(.$func)*),
+            $crate::detect_not_null!($(.$func)*),
+            $crate::detect_unique!($(.$func)*),
+            $crate::detect_default!($(.$func)*),
+            $crate::detect_default_fn!($(.$func)*),
+            fn() -> Result<$crate::common::Number, ::std::fmt::Error>,
+            $crate::detect_update_fn!($(.$func)*),
+            fn() -> Result<$crate::common::Number, ::std::fmt::Error>,
+            $crate::detect_references!($(.$func)*),
+            $crate::detect_generated!($(.$func)*),
         >
     };
 }
 
 // Detect specific function and assign corresponding type for PrimaryKey
+#[doc(hidden)]
+#[macro_export]
 macro_rules! detect_primary_key {
-    (.primary $(.$func:ident)*) => { IsPrimary };
+    (.primary $(.$func:ident)*) => { $crate::columns::IsPrimary };
     (.$head:ident $(.$func:ident)*) => {
-        detect_primary_key!($(.$func)*)
+        $crate::detect_primary_key!($(.$func)*)
     };
-    () => { NotPrimary };
+    () => { $crate::columns::NotPrimary };
 }
 
 // Detect specific function and assign corresponding type for NotNull
+#[doc(hidden)]
+#[macro_export]
 macro_rules! detect_not_null {
-    ($(.)?not_null $(.$func:ident)*) => {
-        NotNullable
+    (.not_null $(.$func:ident)*) => {
+        $crate::columns::NotNullable
     };
-    ($(.)?$head:ident $(.$func:ident)*) => {
-        detect_not_null!($(.$func)*)
+    (.$head:ident $(.$func:ident)*) => {
+        $crate::detect_not_null!($(.$func)*)
     };
-    ($(.)?) => {
-        Nullable
+    () => {
+        $crate::columns::Nullable
     };
 }
 
 // Detect specific function and assign corresponding type for Unique
+#[doc(hidden)]
+#[macro_export]
 macro_rules! detect_unique {
     (.unique $(.$func:ident)*) => {
-        IsUnique
+        $crate::columns::IsUnique
     };
     (.$head:ident $(.$func:ident)*) => {
-        detect_unique!($(.$func)*)
+        $crate::detect_unique!($(.$func)*)
     };
-    ($(.)?) => {
-        NotUnique
+    () => {
+        $crate::columns::NotUnique
     };
 }
 
 // Detect specific function and assign corresponding type for Autoincremented
+#[doc(hidden)]
+#[macro_export]
 macro_rules! detect_autoincrement {
     (.autoincrement $(.$func:ident)*) => {
-        IsAutoIncremented
+        $crate::columns::integer::IsAutoIncremented
     };
     (.$head:ident $(.$func:ident)*) => {
-        detect_autoincrement!($(.$func)*)
+        $crate::detect_autoincrement!($(.$func)*)
     };
     () => {
-        NotAutoIncremented
+        $crate::columns::integer::NotAutoIncremented
     };
 }
 
 // Detect specific function and assign corresponding type for Default
+#[doc(hidden)]
+#[macro_export]
 macro_rules! detect_default {
     (.default $(.$func:ident)*) => {
-        DefaultSet
+        $crate::columns::DefaultSet
+    };
+    (.default_sql $(.$func:ident)*) => {
+        $crate::columns::DefaultSet
     };
     (.$head:ident $(.$func:ident)*) => {
-        detect_default!($(.$func)*)
+        $crate::detect_default!($(.$func)*)
     };
     () => {
-        DefaultNotSet
+        $crate::columns::DefaultNotSet
     };
 }
 
 // Detect specific function and assign corresponding type for DefaultFn
+#[doc(hidden)]
+#[macro_export]
 macro_rules! detect_default_fn {
-    ($(.)?default_fn $(.$func:ident)*) => {
-        $crate::DefaultFnSet
+    (.default_fn $(.$func:ident)*) => {
+        $crate::columns::DefaultFnSet
     };
-    ($(.)?$head:ident $(.$func:ident)*) => {
-        detect_default_fn!($(.$func)*)
+    (.$head:ident $(.$func:ident)*) => {
+        $crate::detect_default_fn!($(.$func)*)
     };
     () => {
-        DefaultFnNotSet
+        $crate::columns::DefaultFnNotSet
     };
 }
 
-macro_rules! sqlite_table {
-    ($table_name:expr, { $($field_name:ident : $func:tt),* }) => {{
-            let $( $field_name = $func; )*
-    }};
-    ($table_name:expr, { $($field_name:ident : $type:ident ( $($type_args:tt)* ) $(.$func:ident ( $($args:expr)* ))*),* $(,)? }) => {
-        paste::paste! {
-
-            // stringify!($( $field_name: sqlite_column_type!($type $(.$func)*), )*);
-            // stringify!($($type($($type_args)*)$(.$func($($args),*))*),*);
-            // struct [<$table_name:camel>] {
-                // $( $field_name: sqlite_column_type!($type $(.$func)*), )*
-            // }
-
-            $( let $field_name = $type($($type_args)*)$(.$func($($args),*))*; )*
-
-            // impl [<$table_name:camel>] {
-            //     pub fn new() -> Self {
-            //         [<$table_name:camel>] {
-            //             $( $field_name: $type($($type_args)*)$(.$func($($args),*))*, )*
-            //         }
-            //     }
-            // }
-        }
+// Detect specific function and assign corresponding type for UpdateFn
+#[doc(hidden)]
+#[macro_export]
+macro_rules! detect_update_fn {
+    (.on_update_fn $(.$func:ident)*) => {
+        $crate::columns::UpdateFnSet
+    };
+    (.on_update_sql $(.$func:ident)*) => {
+        $crate::columns::UpdateFnSet
+    };
+    (.$head:ident $(.$func:ident)*) => {
+        $crate::detect_update_fn!($(.$func)*)
+    };
+    () => {
+        $crate::columns::UpdateFnNotSet
     };
 }
 
-#[cfg(test)]
-mod tests {
+// Detect specific function and assign corresponding type for References
+#[doc(hidden)]
+#[macro_export]
+macro_rules! detect_references {
+    (.references $(.$func:ident)*) => {
+        $crate::columns::ReferenceSet
+    };
+    (.$head:ident $(.$func:ident)*) => {
+        $crate::detect_references!($(.$func)*)
+    };
+    () => {
+        $crate::columns::ReferenceNotSet
+    };
+}
 
-    use std::{collections::HashMap, fmt::Display};
+// Detect specific function and assign corresponding type for Generated
+#[doc(hidden)]
+#[macro_export]
+macro_rules! detect_generated {
+    (.generated_always_as $(.$func:ident)*) => {
+        $crate::columns::GeneratedSet
+    };
+    (.$head:ident $(.$func:ident)*) => {
+        $crate::detect_generated!($(.$func)*)
+    };
+    () => {
+        $crate::columns::GeneratedNotSet
+    };
+}
 
-    use ::common::traits::{
-        ColumnBuilder, Comparable, DefaultFn, DefaultValue, NotNull, PrimaryKey, Unique,
+// Detect the without_rowid table option and assign corresponding type for RowID
+#[doc(hidden)]
+#[macro_export]
+macro_rules! detect_row_id {
+    (without_rowid $($option:ident)*) => {
+        $crate::table::WithoutRowID
     };
-    use columns::{
-        integer::{self, integer, SQLiteIntegerMode},
-        real::real,
-        text::{text, SQLiteTextMode},
-        SQLiteColumn,
+    ($head:ident $($option:ident)*) => {
+        $crate::detect_row_id!($($option)*)
     };
-    use traits::column::*;
+    () => {
+        $crate::table::WithRowID
+    };
+}
 
-    use super::*;
+// Applies the builder chain of a column, casting closures to the fn pointers named by
+// sqlite_column_type so they coerce anywhere in the chain, not only in the last call
+#[doc(hidden)]
+#[macro_export]
+macro_rules! sqlite_column {
+    ($column:expr;) => {
+        $column
+    };
+    ($column:expr; .default_fn($fun:expr) $($rest:tt)*) => {
+        $crate::sqlite_column!($column.default_fn($fun as fn() -> Result<_, ::std::fmt::Error>); $($rest)*)
+    };
+    ($column:expr; .on_update_fn($fun:expr) $($rest:tt)*) => {
+        $crate::sqlite_column!($column.on_update_fn($fun as fn() -> Result<_, ::std::fmt::Error>); $($rest)*)
+    };
+    ($column:expr; .$func:ident($($args:expr),*) $($rest:tt)*) => {
+        $crate::sqlite_column!($column.$func($($args),*); $($rest)*)
+    };
+}
 
-    #[test]
-    fn table() {
-        fn eq<Lhs, Rhs>(lhs: &Lhs, rhs: &Rhs)
-        where
-            Lhs: Comparable<Rhs>,
-        {
+/// Declares a SQLite table as a struct holding one typed column per field.
+///
+/// Table-level constraints and indexes can follow the columns as `|table| [...]`, where `table`
+/// borrows the declared struct. Table options (`strict`, `without_rowid`, `if_not_exists`) come
+/// last in brackets. `SQLiteDefinition` renders the `CREATE TABLE` statement followed by one
+/// `CREATE INDEX` statement per index, separated by `;` so they can run as a batch.
+///
+/// ```ignore
+/// sqlite_table!("users_table", {
+///     id: integer("id", SQLiteIntegerMode::Number).primary().autoincrement(),
+///     name: text("name", SQLiteTextMode::String).not_null(),
+/// }, [strict]);
+///
+/// sqlite_table!("memberships", {
+///     user_id: integer("user_id", SQLiteIntegerMode::Number).not_null(),
+///     group_id: integer("group_id", SQLiteIntegerMode::Number).not_null(),
+/// }, |table| [
+///     primary_key((&table.user_id, &table.group_id)),
+///     foreign_key((&table.user_id,)).references(|| (UsersTable::new().id,)),
+///     index("memberships_group_idx").on((&table.group_id,)),
+/// ]);
+///
+/// let users = UsersTable::new();
+/// assert_eq!(UsersTable::NAME, "users_table");
+/// assert_eq!(users.id.name(), "id");
+/// ```
+#[macro_export]
+macro_rules! sqlite_table {
+    ($table_name:literal, { $($field_name:ident : $type:ident $(::<$data_type:ty>)? ( $($type_args:tt)* ) $(.$func:ident ( $($args:expr),* ))*),* $(,)? } $(, |$table:ident| [$($constraint:expr),* $(,)?])? $(, [$($option:ident),* $(,)?])? $(,)?) => {
+        $crate::paste::paste! {
+            #[derive(Debug, Clone)]
+            pub struct [<$table_name:camel>] {
+                $( pub $field_name: $crate::sqlite_column_type!($type [$($data_type)?] ($($type_args)*) $(.$func)*), )*
+            }
+
+            #[derive(Debug, Clone, Default)]
+            pub struct [<Insert $table_name:camel>] {
+                $(
+                    pub $field_name: Option<
+                        <$crate::sqlite_column_type!($type [$($data_type)?] ($($type_args)*) $(.$func)*) as $crate::traits::column::SQLiteColumnData>::Writable
+                    >,
+                )*
+            }
+
+            #[derive(Debug, Clone, Default)]
+            pub struct [<Update $table_name:camel>] {
+                $(
+                    pub $field_name: Option<
+                        <$crate::sqlite_column_type!($type [$($data_type)?] ($($type_args)*) $(.$func)*) as $crate::traits::column::SQLiteColumnData>::Assignable
+                    >,
+                )*
+            }
+
+            const _: () = {
+                type RowID = $crate::detect_row_id!($($($option)*)?);
+                $(
+                    $crate::table::check_column::<
+                        $crate::detect_primary_key!($(.$func)*),
+                        $crate::detect_unique!($(.$func)*),
+                        $crate::detect_autoincrement!($(.$func)*),
+                        RowID,
+                    >();
+                )*
+            };
+
+            impl [<$table_name:camel>] {
+                pub fn new() -> Self {
+                    #[allow(unused_imports)]
+                    use $crate::prelude::*;
+
+                    Self {
+                        $( $field_name: $crate::sqlite_column!($crate::columns::$type::$type $(::<$data_type, _>)? ($($type_args)*); $(.$func($($args),*))*).in_table($table_name), )*
+                    }
+                }
+            }
+
+            impl $crate::prelude::SQLFragment<$crate::common::SQLiteValue> for [<$table_name:camel>] {
+                fn into_fragment(self) -> $crate::prelude::SQL<$crate::common::SQLiteValue> {
+                    $crate::prelude::SQL::table($table_name)
+                }
+            }
+
+            impl $crate::prelude::SQLFragment<$crate::common::SQLiteValue> for &[<$table_name:camel>] {
+                fn into_fragment(self) -> $crate::prelude::SQL<$crate::common::SQLiteValue> {
+                    $crate::prelude::SQL::table($table_name)
+                }
+            }
+
+            impl Default for [<$table_name:camel>] {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
+            impl $crate::traits::table::SQLiteTable for [<$table_name:camel>] {
+                const NAME: &'static str = $table_name;
+
+                type Insert = [<Insert $table_name:camel>];
+
+                fn insert_values(
+                    &self,
+                    values: Self::Insert,
+                ) -> Result<
+                    Vec<(&'static str, $crate::prelude::SQL<$crate::common::SQLiteValue>)>,
+                    $crate::query::insert::SQLiteInsertError,
+                > {
+                    use $crate::prelude::*;
+
+                    let mut columns = vec![];
+                    $(
+                        if let Some(value) = self.$field_name.insert_value(values.$field_name)? {
+                            columns.push((self.$field_name.name(), value));
+                        }
+                    )*
+
+                    Ok(columns)
+                }
+
+                type Update = [<Update $table_name:camel>];
+
+                fn update_values(
+                    &self,
+                    values: Self::Update,
+                ) -> Result<
+                    Vec<(&'static str, $crate::prelude::SQL<$crate::common::SQLiteValue>)>,
+                    $crate::query::update::SQLiteUpdateError,
+                > {
+                    use $crate::prelude::*;
+
+                    let mut columns = vec![];
+                    $(
+                        if let Some(value) = self.$field_name.update_value(values.$field_name)? {
+                            columns.push((self.$field_name.name(), value));
+                        }
+                    )*
+
+                    Ok(columns)
+                }
+            }
+
+            impl $crate::traits::table::SQLiteDefinition for [<$table_name:camel>] {
+                fn definition(
+                    self,
+                ) -> Result<
+                    $crate::prelude::SQL<$crate::common::SQLiteValue>,
+                    $crate::common::SQLiteDefinitionError,
+                > {
+                    #[allow(unused_imports)]
+                    use $crate::prelude::*;
+
+                    #[allow(unused_mut)]
+                    let mut constraints: Vec<SQL<$crate::common::SQLiteValue>> = vec![];
+                    #[allow(unused_mut)]
+                    let mut indexes: Vec<SQL<$crate::common::SQLiteValue>> = vec![];
+                    $(
+                        let $table = &self;
+                        $(
+                            match $constraint.into_extra(Self::NAME)? {
+                                $crate::traits::table::SQLiteTableExtra::Constraint(sql) => constraints.push(sql),
+                                $crate::traits::table::SQLiteTableExtra::Index(sql) => indexes.push(sql),
+                            }
+                        )*
+                    )?
+
+                    let mut definitions = vec![$( self.$field_name.definition()?, )*];
+                    definitions.append(&mut constraints);
+
+                    let mut statements = vec![$crate::table::SQLiteTableBuilder::new(Self::NAME)
+                        $($(.$option())*)?
+                        .create(definitions)];
+                    statements.append(&mut indexes);
+
+                    Ok(SQL::join(statements, ";\n"))
+                }
+            }
         }
+    };
+}
 
-        struct SQLiteTable {
-            id: Box<SQLiteColumn<DT, CT, DM, P, N, U, A, D, F, Func>>,
+/// Declares an enum for `SQLiteTextMode::Enum` columns, stored as the text given for each variant.
+/// The enum derives `Clone` and `Copy` and can be compared against the column in expressions.
+///
+/// ```
+/// use sqlite::{prelude::*, sqlite_enum, sqlite_table};
+///
+/// sqlite_enum! {
+///     #[derive(Debug, PartialEq)]
+///     pub enum Role {
+///         Admin = "admin",
+///         Member = "member",
+///     }
+/// }
+///
+/// sqlite_table!("members", {
+///     role: text::<Role>("role", SQLiteTextMode::Enum).not_null().check_values("members_role"),
+/// });
+///
+/// assert_eq!(Role::VALUES, ["admin", "member"]);
+/// assert_eq!(Role::from_text("member"), Some(Role::Member));
+/// ```
+#[macro_export]
+macro_rules! sqlite_enum {
+    ($(#[$attr:meta])* $vis:vis enum $name:ident { $($variant:ident = $text:literal),* $(,)? }) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy)]
+        $vis enum $name {
+            $( $variant, )*
         }
 
-        impl SQLiteTable {
-            fn column<
-                DataType: Default + Clone,
-                ColumnType: Default + Clone,
-                DataMode: Default + Clone,
-                TPrimary: PrimaryKey,
-                TNotNull: NotNull,
-                TUnique: Unique,
-                TAuto: Autoincrement,
-                TDefault: DefaultValue,
-                TDefaultFn: DefaultFn,
-            >(
-                &mut self,
-                column: SQLiteColumn<
-                    DataType,
-                    ColumnType,
-                    DataMode,
-                    TPrimary,
-                    TNotNull,
-                    TUnique,
-                    TAuto,
-                    TDefault,
-                    TDefaultFn,
-                >,
-            ) -> &mut Self {
-                self
+        impl $crate::columns::text::SQLiteEnum for $name {
+            const VALUES: &'static [&'static str] = &[$( $text ),*];
+
+            fn as_text(&self) -> &'static str {
+                match self {
+                    $( Self::$variant => $text, )*
+                }
             }
 
-            // fn columns(&mut self, columns: Iterator<Item = dyn ColumnBuilder>) {}
+            fn from_text(text: &str) -> Option<Self> {
+                match text {
+                    $( $text => Some(Self::$variant), )*
+                    _ => None,
+                }
+            }
         }
 
-        let table = SQLiteTable {}
-            .column(integer("id", SQLiteIntegerMode::Number))
-            .column(real("value"));
-
-        macro_rules! sql {
-            // Capture the format string and a list of expressions
-            ($($arg:tt)*) => {{
-                // Use format_args! to generate a formatted string
-                let query = format!($($arg)*);
-                // SQL { query }
-                query
-            }};
-        }
+        impl $crate::prelude::Comparable<$name> for $name {}
+        impl $crate::prelude::Comparable<&$name> for $name {}
 
-        // let sql = sql!("SELECT * FROM users WHERE id = {}", table.id);
+        impl $crate::prelude::Expression<$crate::common::SQLiteValue> for $name {
+            type DataType = $name;
 
-        fn my_fn() -> Result<i64, std::fmt::Error> {
-            Ok(42)
+            fn into_sql(self) -> $crate::prelude::SQL<$crate::common::SQLiteValue, $name> {
+                $crate::prelude::SQL::param($crate::common::SQLiteValue::Text(
+                    $crate::columns::text::SQLiteEnum::as_text(&self).to_string(),
+                ))
+                .cast()
+            }
         }
 
-        // sqlite_table!("users_table", {
-        //     id: integer("id", SQLiteIntegerMode::Number).primary().not_null().default(42),
-        //     // name: text("name", SQLiteTextMode::String).primary().not_null(),
-        // });
+        impl $crate::prelude::SQLFragment<$crate::common::SQLiteValue> for $name {
+            fn into_fragment(self) -> $crate::prelude::SQL<$crate::common::SQLiteValue> {
+                $crate::prelude::Expression::into_sql(self).cast()
+            }
+        }
+    };
+}
 
-        // let users = &UsersTable::new();
-        // let id = &users.id;
-        // let name = &users.name;
+#[cfg(test)]
+mod tests {
+    use std::{
+        fmt,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+
+    use crate::prelude::*;
+
+    fn created_at() -> Result<SystemTime, fmt::Error> {
+        Ok(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+    }
+
+    sqlite_table!("users_table", {
+        id: integer("id", SQLiteIntegerMode::Number).primary().autoincrement(),
+        name: text("name", SQLiteTextMode::String).not_null().unique("users_name_unique"),
+        score: real("score").default(1.5),
+        avatar: blob("avatar", SQLiteBlobMode::Buffer),
+        created_at: integer("created_at", SQLiteIntegerMode::Timestamp).default_fn(created_at),
+    });
 
-        // let f = 42;
+    #[test]
+    fn table() {
+        let users = UsersTable::new();
+
+        assert_eq!(UsersTable::NAME, "users_table");
+        assert_eq!(users.id.name(), "id");
+        assert_eq!(users.name.name(), "name");
+        assert_eq!(users.score.default, Some(1.5));
+        assert_eq!(users.avatar.name(), "avatar");
+        assert_eq!(
+            users.created_at.default_fn.map(|f| f()),
+            Some(Ok(UNIX_EPOCH + Duration::from_secs(1_700_000_000)))
+        );
+    }
+
+    #[test]
+    fn table_with_closure_default_fn() {
+        sqlite_table!("posts", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            slug: text("slug", SQLiteTextMode::String)
+                .default_fn(|| Ok("untitled".to_string()))
+                .on_update_fn(|| Ok("edited".to_string())),
+        });
 
-        // let n = users.id.name();
+        let posts = Posts::default();
 
-        // println!("NAME NAME NAME NAME NAME: {n}");
+        assert_eq!(Posts::NAME, "posts");
+        assert_eq!(posts.id.name(), "id");
+        assert_eq!(
+            posts.slug.default_fn.map(|f| f()),
+            Some(Ok("untitled".to_string()))
+        );
+        assert_eq!(
+            posts.slug.on_update_fn.map(|f| f()),
+            Some(Ok("edited".to_string()))
+        );
     }
 }
@@ -0,0 +1,250 @@
+use std::{error::Error, fmt};
+
+use common::{ToSQL, SQL};
+
+use crate::{
+    common::{SQLiteDialect, SQLiteEncodeError, SQLiteValue},
+    traits::table::SQLiteTable,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SQLiteInsertError {
+    /// The `default_fn` of an omitted column returned an error.
+    DefaultFn {
+        column: &'static str,
+        source: fmt::Error,
+    },
+    /// The value of a column cannot be converted to what its mode stores.
+    Encode {
+        column: &'static str,
+        source: SQLiteEncodeError,
+    },
+}
+
+impl fmt::Display for SQLiteInsertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DefaultFn { column, .. } => {
+                write!(f, r#"default_fn of column "{column}" failed"#)
+            }
+            Self::Encode { column, .. } => {
+                write!(f, r#"value of column "{column}" cannot be stored"#)
+            }
+        }
+    }
+}
+
+impl Error for SQLiteInsertError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::DefaultFn { source, .. } => Some(source),
+            Self::Encode { source, .. } => Some(source),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SQLiteInsertBuilder<'a, T: SQLiteTable> {
+    table: &'a T,
+}
+
+/// Starts an `INSERT` into `table`.
+pub fn insert<T: SQLiteTable>(table: &T) -> SQLiteInsertBuilder<'_, T> {
+    SQLiteInsertBuilder { table }
+}
+
+impl<T: SQLiteTable> SQLiteInsertBuilder<'_, T> {
+    /// Binds a row, filling omitted columns from their `default_fn`.
+    pub fn values(self, values: T::Insert) -> Result<SQLiteInsert, SQLiteInsertError> {
+        let (columns, params): (Vec<_>, Vec<_>) =
+            self.table.insert_values(values)?.into_iter().unzip();
+
+        let sql = SQL::raw("INSERT INTO ").append(SQL::table(T::NAME));
+        let sql = if columns.is_empty() {
+            sql.push_str(" DEFAULT VALUES")
+        } else {
+            sql.push_str(" (")
+                .append(SQL::<SQLiteValue>::join(
+                    columns.into_iter().map(SQL::name),
+                    ", ",
+                ))
+                .push_str(") VALUES (")
+                .append(SQL::join(params, ", "))
+                .push_str(")")
+        };
+
+        Ok(SQLiteInsert { sql })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SQLiteInsert {
+    sql: SQL<SQLiteValue>,
+}
+
+impl SQLiteInsert {
+    /// Values bound to the statement's `?` placeholders, in order.
+    pub fn params(&self) -> Vec<SQLiteValue> {
+        self.sql.params().into_iter().cloned().collect()
+    }
+
+    pub fn into_sql(self) -> SQL<SQLiteValue> {
+        self.sql
+    }
+}
+
+impl ToSQL for SQLiteInsert {
+    fn to_sql(self) -> String {
+        self.sql.build::<SQLiteDialect>().0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, error::Error, fmt};
+
+    use crate::{
+        common::{SQLiteEncodeError, SQLiteValue},
+        prelude::*,
+    };
+
+    use super::SQLiteInsertError;
+
+    fn next_id() -> Result<String, fmt::Error> {
+        Ok("generated".to_string())
+    }
+
+    fn failing_id() -> Result<String, fmt::Error> {
+        Err(fmt::Error)
+    }
+
+    crate::sqlite_table!("notes", {
+        id: text("id", SQLiteTextMode::String).primary().default_fn(next_id),
+        body: text("body", SQLiteTextMode::String).not_null(),
+        pinned: integer("pinned", SQLiteIntegerMode::Boolean).default(false),
+    });
+
+    crate::sqlite_table!("drafts", {
+        id: text("id", SQLiteTextMode::String).primary().default_fn(failing_id),
+    });
+
+    #[test]
+    fn default_fn_on_omitted_column() {
+        let notes = Notes::new();
+
+        let statement = insert(&notes)
+            .values(InsertNotes {
+                body: Some("hello".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            statement.params(),
+            [
+                SQLiteValue::Text("generated".to_string()),
+                SQLiteValue::Text("hello".to_string())
+            ]
+        );
+        assert_eq!(
+            statement.clone().to_sql(),
+            r#"INSERT INTO "notes" ("id", "body") VALUES (?, ?)"#
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&notes.to_sql().unwrap(), ()).unwrap();
+        conn.execute(
+            &statement.clone().to_sql(),
+            rusqlite::params_from_iter(statement.params()),
+        )
+        .unwrap();
+
+        let row: (String, String, i64) = conn
+            .query_row(r#"SELECT "id", "body", "pinned" FROM "notes""#, (), |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .unwrap();
+
+        assert_eq!(row, ("generated".to_string(), "hello".to_string(), 0));
+    }
+
+    #[test]
+    fn explicit_value_skips_default_fn() {
+        let statement = insert(&Notes::new())
+            .values(InsertNotes {
+                id: Some("explicit".to_string()),
+                body: Some("hello".to_string()),
+                pinned: Some(true),
+            })
+            .unwrap();
+
+        assert_eq!(
+            statement.params(),
+            [
+                SQLiteValue::Text("explicit".to_string()),
+                SQLiteValue::Text("hello".to_string()),
+                SQLiteValue::Integer(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn default_fn_error() {
+        let error = insert(&Drafts::new())
+            .values(InsertDrafts::default())
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            SQLiteInsertError::DefaultFn {
+                column: "id",
+                source: fmt::Error
+            }
+        );
+        assert_eq!(error.to_string(), r#"default_fn of column "id" failed"#);
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn encode_error() {
+        crate::sqlite_table!("boards", {
+            grid: text::<BTreeMap<(i32, i32), i32>>("grid", SQLiteTextMode::JSON),
+        });
+
+        let error = insert(&Boards::new())
+            .values(InsertBoards {
+                grid: Some(BTreeMap::from([((0, 0), 1)])),
+            })
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            SQLiteInsertError::Encode {
+                column: "grid",
+                source: SQLiteEncodeError::new("JSON text", "key must be a string")
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            r#"value of column "grid" cannot be stored"#
+        );
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn default_values() {
+        crate::sqlite_table!("counters", {
+            hits: integer("hits", SQLiteIntegerMode::Number).default(0),
+        });
+
+        let statement = insert(&Counters::new())
+            .values(InsertCounters::default())
+            .unwrap();
+
+        assert!(statement.params().is_empty());
+        assert_eq!(
+            statement.to_sql(),
+            r#"INSERT INTO "counters" DEFAULT VALUES"#
+        );
+    }
+}
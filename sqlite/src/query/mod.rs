@@ -0,0 +1,2 @@
+pub mod insert;
+pub mod update;
@@ -0,0 +1,364 @@
+use std::{error::Error, fmt};
+
+use common::{expressions::conditions::and, ToSQL, SQL};
+
+use crate::{
+    common::{SQLiteDialect, SQLiteEncodeError, SQLiteValue},
+    traits::table::SQLiteTable,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SQLiteUpdateError {
+    /// The `on_update_fn` of a column left out of the update returned an error.
+    OnUpdateFn {
+        column: &'static str,
+        source: fmt::Error,
+    },
+    /// The value of a column cannot be converted to what its mode stores.
+    Encode {
+        column: &'static str,
+        source: SQLiteEncodeError,
+    },
+    /// Neither the given values nor any `on_update_fn`/`on_update_sql` assign a column.
+    NoValues,
+}
+
+impl fmt::Display for SQLiteUpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OnUpdateFn { column, .. } => {
+                write!(f, r#"on_update_fn of column "{column}" failed"#)
+            }
+            Self::Encode { column, .. } => {
+                write!(f, r#"value of column "{column}" cannot be stored"#)
+            }
+            Self::NoValues => write!(f, "update does not set any column"),
+        }
+    }
+}
+
+impl Error for SQLiteUpdateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OnUpdateFn { source, .. } => Some(source),
+            Self::Encode { source, .. } => Some(source),
+            Self::NoValues => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SQLiteUpdateBuilder<'a, T: SQLiteTable> {
+    table: &'a T,
+}
+
+/// Starts an `UPDATE` of `table`.
+pub fn update<T: SQLiteTable>(table: &T) -> SQLiteUpdateBuilder<'_, T> {
+    SQLiteUpdateBuilder { table }
+}
+
+impl<T: SQLiteTable> SQLiteUpdateBuilder<'_, T> {
+    /// Assigns the given columns, filling the others from their `on_update_fn`/`on_update_sql`.
+    pub fn set(self, values: T::Update) -> Result<SQLiteUpdate, SQLiteUpdateError> {
+        let columns = self.table.update_values(values)?;
+
+        if columns.is_empty() {
+            return Err(SQLiteUpdateError::NoValues);
+        }
+
+        let set = columns
+            .into_iter()
+            .map(|(column, value)| SQL::name(column).push_str(" = ").append(value));
+
+        Ok(SQLiteUpdate {
+            sql: SQL::raw("UPDATE ")
+                .append(SQL::table(T::NAME))
+                .push_str(" SET ")
+                .append(SQL::<SQLiteValue>::join(set, ", ")),
+            where_clause: None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SQLiteUpdate {
+    sql: SQL<SQLiteValue>,
+    where_clause: Option<SQL<SQLiteValue, bool>>,
+}
+
+impl SQLiteUpdate {
+    /// Restricts the update to the rows matching `condition`; further calls must match as well.
+    pub fn where_(self, condition: SQL<SQLiteValue, bool>) -> Self {
+        Self {
+            where_clause: Some(match self.where_clause {
+                Some(where_clause) => and([where_clause, condition]),
+                None => condition,
+            }),
+            ..self
+        }
+    }
+
+    /// Values bound to the statement's `?` placeholders, in order.
+    pub fn params(&self) -> Vec<SQLiteValue> {
+        let mut params = self.sql.params();
+        if let Some(where_clause) = &self.where_clause {
+            params.extend(where_clause.params());
+        }
+
+        params.into_iter().cloned().collect()
+    }
+
+    pub fn into_sql(self) -> SQL<SQLiteValue> {
+        match self.where_clause {
+            Some(condition) => self.sql.push_str(" WHERE ").append(condition),
+            None => self.sql,
+        }
+    }
+}
+
+impl ToSQL for SQLiteUpdate {
+    fn to_sql(self) -> String {
+        self.into_sql().build::<SQLiteDialect>().0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::BTreeMap,
+        error::Error,
+        fmt,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+
+    use common::expressions::conditions::{eq, ne};
+
+    use crate::{
+        common::{SQLiteEncodeError, SQLiteValue},
+        prelude::*,
+    };
+
+    use super::SQLiteUpdateError;
+
+    fn now() -> Result<SystemTime, fmt::Error> {
+        Ok(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+    }
+
+    fn broken_clock() -> Result<SystemTime, fmt::Error> {
+        Err(fmt::Error)
+    }
+
+    crate::sqlite_table!("posts", {
+        id: integer("id", SQLiteIntegerMode::Number).primary(),
+        title: text("title", SQLiteTextMode::String).not_null(),
+        updated_at: integer("updated_at", SQLiteIntegerMode::Timestamp).on_update_fn(now),
+        touched_at: text("touched_at", SQLiteTextMode::String).on_update_sql(CURRENT_TIMESTAMP),
+    });
+
+    crate::sqlite_table!("drafts", {
+        id: integer("id", SQLiteIntegerMode::Number).primary(),
+        updated_at: integer("updated_at", SQLiteIntegerMode::Timestamp).on_update_fn(broken_clock),
+    });
+
+    #[test]
+    fn on_update_for_omitted_columns() {
+        let posts = Posts::new();
+
+        let statement = update(&posts)
+            .set(UpdatePosts {
+                title: Some("hello".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            statement.params(),
+            [
+                SQLiteValue::Text("hello".to_string()),
+                SQLiteValue::Integer(1_700_000_000)
+            ]
+        );
+        assert_eq!(
+            statement.clone().to_sql(),
+            r#"UPDATE "posts" SET "title" = ?, "updated_at" = ?, "touched_at" = (CURRENT_TIMESTAMP)"#
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&posts.to_sql().unwrap(), ()).unwrap();
+        conn.execute(
+            r#"INSERT INTO "posts" ("id", "title") VALUES (1, 'draft')"#,
+            (),
+        )
+        .unwrap();
+        conn.execute(
+            &statement.clone().to_sql(),
+            rusqlite::params_from_iter(statement.params()),
+        )
+        .unwrap();
+
+        let row: (String, i64, String) = conn
+            .query_row(
+                r#"SELECT "title", "updated_at", "touched_at" FROM "posts""#,
+                (),
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+
+        assert_eq!((row.0.as_str(), row.1), ("hello", 1_700_000_000));
+        assert!(!row.2.is_empty());
+    }
+
+    #[test]
+    fn explicit_value_skips_on_update() {
+        let statement = update(&Posts::new())
+            .set(UpdatePosts {
+                updated_at: Some(Some(UNIX_EPOCH + Duration::from_millis(42_900))),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(statement.params(), [SQLiteValue::Integer(42)]);
+    }
+
+    #[test]
+    fn set_null() {
+        let statement = update(&Posts::new())
+            .set(UpdatePosts {
+                updated_at: Some(None),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert!(statement.params().is_empty());
+        assert_eq!(
+            statement.to_sql(),
+            r#"UPDATE "posts" SET "updated_at" = NULL, "touched_at" = (CURRENT_TIMESTAMP)"#
+        );
+    }
+
+    #[test]
+    fn where_clause() {
+        let posts = Posts::new();
+
+        let statement = update(&posts)
+            .set(UpdatePosts {
+                title: Some("hello".to_string()),
+                updated_at: Some(None),
+                ..Default::default()
+            })
+            .unwrap()
+            .where_(eq(&posts.id, 1))
+            .where_(ne(&posts.title, "hello"));
+
+        assert_eq!(
+            statement.params(),
+            [
+                SQLiteValue::Text("hello".to_string()),
+                SQLiteValue::Integer(1),
+                SQLiteValue::Text("hello".to_string())
+            ]
+        );
+        assert_eq!(
+            statement.clone().to_sql(),
+            r#"UPDATE "posts" SET "title" = ?, "updated_at" = NULL, "touched_at" = (CURRENT_TIMESTAMP) WHERE (("posts"."id" = ?) AND ("posts"."title" <> ?))"#
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&posts.clone().to_sql().unwrap(), ()).unwrap();
+        conn.execute(
+            r#"INSERT INTO "posts" ("id", "title", "updated_at") VALUES (1, 'draft', 5), (2, 'draft', 5)"#,
+            (),
+        )
+        .unwrap();
+        assert_eq!(
+            conn.execute(
+                &statement.clone().to_sql(),
+                rusqlite::params_from_iter(statement.params()),
+            )
+            .unwrap(),
+            1
+        );
+
+        let rows: Vec<(i64, String, Option<i64>)> = conn
+            .prepare(r#"SELECT "id", "title", "updated_at" FROM "posts" ORDER BY "id""#)
+            .unwrap()
+            .query_map((), |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(
+            rows,
+            [
+                (1, "hello".to_string(), None),
+                (2, "draft".to_string(), Some(5))
+            ]
+        );
+
+        let statement = update(&posts)
+            .set(UpdatePosts {
+                title: Some("hello".to_string()),
+                ..Default::default()
+            })
+            .unwrap()
+            .where_(sql!(<bool> "{} = 1 OR {} = 2", &posts.id, &posts.id))
+            .where_(ne(&posts.title, "hello"));
+
+        assert!(statement.to_sql().ends_with(
+            r#" WHERE (("posts"."id" = 1 OR "posts"."id" = 2) AND ("posts"."title" <> ?))"#
+        ));
+    }
+
+    #[test]
+    fn on_update_fn_error() {
+        let error = update(&Drafts::new())
+            .set(UpdateDrafts::default())
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            SQLiteUpdateError::OnUpdateFn {
+                column: "updated_at",
+                source: fmt::Error
+            }
+        );
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn encode_error() {
+        crate::sqlite_table!("boards", {
+            grid: text::<BTreeMap<(i32, i32), i32>>("grid", SQLiteTextMode::JSON),
+        });
+
+        let error = update(&Boards::new())
+            .set(UpdateBoards {
+                grid: Some(Some(BTreeMap::from([((0, 0), 1)]))),
+            })
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            SQLiteUpdateError::Encode {
+                column: "grid",
+                source: SQLiteEncodeError::new("JSON text", "key must be a string")
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            r#"value of column "grid" cannot be stored"#
+        );
+    }
+
+    #[test]
+    fn no_values() {
+        crate::sqlite_table!("tags", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+        });
+
+        assert_eq!(
+            update(&Tags::new()).set(UpdateTags::default()).unwrap_err(),
+            SQLiteUpdateError::NoValues
+        );
+    }
+}
@@ -0,0 +1,482 @@
+use common::SQL;
+
+use crate::{
+    common::{SQLiteDefinitionError, SQLiteReferenceAction, SQLiteValue},
+    table::check_table,
+    traits::{
+        column::SQLiteColumnRef,
+        table::{IntoSQLiteTableExtra, SQLiteColumns, SQLiteDefinition, SQLiteTableExtra},
+    },
+};
+
+macro_rules! impl_sqlite_columns {
+    ($head:ident $(, $tail:ident)*) => {
+        impl<$head: SQLiteColumnRef, $($tail: SQLiteColumnRef),*> SQLiteColumns for ($head, $($tail,)*) {
+            type DataTypes = ($head::DataType, $($tail::DataType,)*);
+
+            #[allow(non_snake_case)]
+            fn columns(&self) -> Vec<(&'static str, &'static str)> {
+                let ($head, $($tail,)*) = self;
+                vec![
+                    ($head.table_name(), $head.column_name()),
+                    $(($tail.table_name(), $tail.column_name()),)*
+                ]
+            }
+        }
+    };
+}
+
+impl_sqlite_columns!(A);
+impl_sqlite_columns!(A, B);
+impl_sqlite_columns!(A, B, C);
+impl_sqlite_columns!(A, B, C, D);
+impl_sqlite_columns!(A, B, C, D, E);
+impl_sqlite_columns!(A, B, C, D, E, F);
+impl_sqlite_columns!(A, B, C, D, E, F, G);
+impl_sqlite_columns!(A, B, C, D, E, F, G, H);
+
+type Columns = Vec<(&'static str, &'static str)>;
+
+/// Fails unless all `columns` belong to the same table, returning that table.
+fn single_table(columns: &Columns) -> Result<&'static str, SQLiteDefinitionError> {
+    let table = columns.first().map_or("", |&(table, _)| table);
+
+    check_table(table, columns).map(|()| table)
+}
+
+fn column_list(columns: Columns) -> SQL<SQLiteValue> {
+    SQL::default().nest(SQL::<SQLiteValue>::join(
+        columns.into_iter().map(|(_, name)| SQL::name(name)),
+        ", ",
+    ))
+}
+
+/// `PRIMARY KEY` spanning several columns of a table.
+#[derive(Debug, Clone)]
+pub struct SQLitePrimaryKey {
+    columns: Columns,
+}
+
+pub fn primary_key<C: SQLiteColumns>(columns: C) -> SQLitePrimaryKey {
+    SQLitePrimaryKey {
+        columns: columns.columns(),
+    }
+}
+
+impl SQLiteDefinition for SQLitePrimaryKey {
+    fn definition(self) -> Result<SQL<SQLiteValue>, SQLiteDefinitionError> {
+        single_table(&self.columns)?;
+
+        Ok(SQL::raw("PRIMARY KEY ").append(column_list(self.columns)))
+    }
+}
+
+impl IntoSQLiteTableExtra for SQLitePrimaryKey {
+    fn into_extra(self, table: &'static str) -> Result<SQLiteTableExtra, SQLiteDefinitionError> {
+        check_table(table, &self.columns)?;
+
+        Ok(SQLiteTableExtra::Constraint(self.definition()?))
+    }
+}
+
+/// Named `UNIQUE` constraint waiting for the columns it spans.
+#[derive(Debug, Clone, Copy)]
+pub struct SQLiteUniqueBuilder {
+    name: &'static str,
+}
+
+pub fn unique(name: &'static str) -> SQLiteUniqueBuilder {
+    SQLiteUniqueBuilder { name }
+}
+
+impl SQLiteUniqueBuilder {
+    pub fn on<C: SQLiteColumns>(self, columns: C) -> SQLiteUnique {
+        SQLiteUnique {
+            name: self.name,
+            columns: columns.columns(),
+        }
+    }
+}
+
+/// Named `UNIQUE` constraint spanning several columns of a table.
+#[derive(Debug, Clone)]
+pub struct SQLiteUnique {
+    name: &'static str,
+    columns: Columns,
+}
+
+impl SQLiteDefinition for SQLiteUnique {
+    fn definition(self) -> Result<SQL<SQLiteValue>, SQLiteDefinitionError> {
+        single_table(&self.columns)?;
+
+        Ok(SQL::raw("CONSTRAINT ")
+            .append(SQL::name(self.name))
+            .push_str(" UNIQUE ")
+            .append(column_list(self.columns)))
+    }
+}
+
+impl IntoSQLiteTableExtra for SQLiteUnique {
+    fn into_extra(self, table: &'static str) -> Result<SQLiteTableExtra, SQLiteDefinitionError> {
+        check_table(table, &self.columns)?;
+
+        Ok(SQLiteTableExtra::Constraint(self.definition()?))
+    }
+}
+
+/// `FOREIGN KEY` waiting for the columns it references.
+#[derive(Debug, Clone)]
+pub struct SQLiteForeignKeyBuilder<C: SQLiteColumns> {
+    columns: C,
+}
+
+pub fn foreign_key<C: SQLiteColumns>(columns: C) -> SQLiteForeignKeyBuilder<C> {
+    SQLiteForeignKeyBuilder { columns }
+}
+
+impl<C: SQLiteColumns> SQLiteForeignKeyBuilder<C> {
+    /// Points the columns at those returned by `columns`, which must hold the same data types
+    /// in the same order.
+    pub fn references<R, F>(self, columns: F) -> SQLiteForeignKey
+    where
+        R: SQLiteColumns<DataTypes = C::DataTypes>,
+        F: Fn() -> R,
+    {
+        SQLiteForeignKey {
+            columns: self.columns.columns(),
+            references: columns().columns(),
+            on_delete: None,
+            on_update: None,
+            deferrable: false,
+        }
+    }
+}
+
+/// `FOREIGN KEY` spanning several columns of a table.
+#[derive(Debug, Clone)]
+pub struct SQLiteForeignKey {
+    columns: Columns,
+    references: Columns,
+    on_delete: Option<SQLiteReferenceAction>,
+    on_update: Option<SQLiteReferenceAction>,
+    deferrable: bool,
+}
+
+impl SQLiteForeignKey {
+    pub fn on_delete(self, action: SQLiteReferenceAction) -> Self {
+        Self {
+            on_delete: Some(action),
+            ..self
+        }
+    }
+
+    pub fn on_update(self, action: SQLiteReferenceAction) -> Self {
+        Self {
+            on_update: Some(action),
+            ..self
+        }
+    }
+
+    /// Defers the foreign key check until the transaction commits.
+    pub fn deferrable(self) -> Self {
+        Self {
+            deferrable: true,
+            ..self
+        }
+    }
+}
+
+impl SQLiteDefinition for SQLiteForeignKey {
+    fn definition(self) -> Result<SQL<SQLiteValue>, SQLiteDefinitionError> {
+        single_table(&self.columns)?;
+        let table = single_table(&self.references)?;
+
+        let mut sql = SQL::raw("FOREIGN KEY ")
+            .append(column_list(self.columns))
+            .push_str(" REFERENCES ")
+            .append(SQL::table(table))
+            .push_str(" ")
+            .append(column_list(self.references));
+
+        if let Some(action) = self.on_delete {
+            sql = sql.push_str(format!(" ON DELETE {action}"));
+        }
+        if let Some(action) = self.on_update {
+            sql = sql.push_str(format!(" ON UPDATE {action}"));
+        }
+        if self.deferrable {
+            sql = sql.push_str(" DEFERRABLE INITIALLY DEFERRED");
+        }
+
+        Ok(sql)
+    }
+}
+
+impl IntoSQLiteTableExtra for SQLiteForeignKey {
+    fn into_extra(self, table: &'static str) -> Result<SQLiteTableExtra, SQLiteDefinitionError> {
+        check_table(table, &self.columns)?;
+
+        Ok(SQLiteTableExtra::Constraint(self.definition()?))
+    }
+}
+
+/// Named table-level `CHECK` constraint.
+#[derive(Debug, Clone)]
+pub struct SQLiteCheck {
+    name: &'static str,
+    expression: SQL<SQLiteValue, bool>,
+}
+
+pub fn check(name: &'static str, expression: SQL<SQLiteValue, bool>) -> SQLiteCheck {
+    SQLiteCheck { name, expression }
+}
+
+impl SQLiteDefinition for SQLiteCheck {
+    fn definition(self) -> Result<SQL<SQLiteValue>, SQLiteDefinitionError> {
+        Ok(SQL::raw("CONSTRAINT ")
+            .append(SQL::name(self.name))
+            .push_str(" CHECK ")
+            .nest(self.expression))
+    }
+}
+
+impl IntoSQLiteTableExtra for SQLiteCheck {
+    fn into_extra(self, table: &'static str) -> Result<SQLiteTableExtra, SQLiteDefinitionError> {
+        check_table(table, &self.expression.columns())?;
+
+        Ok(SQLiteTableExtra::Constraint(self.definition()?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{
+        expressions::conditions::{and, gt, or},
+        traits::ColumnBuilder,
+        InlineError, SQL,
+    };
+
+    use crate::{
+        columns::{
+            integer::{integer, SQLiteIntegerMode},
+            real::real,
+            text::{text, SQLiteTextMode},
+        },
+        common::{SQLiteDefinitionError, SQLiteReferenceAction, SQLiteValue},
+        traits::table::{IntoSQLiteTableExtra, SQLiteDefinition},
+    };
+
+    use super::{check, foreign_key, primary_key, unique};
+
+    #[test]
+    fn to_sql() {
+        let a = integer("a", SQLiteIntegerMode::Number).in_table("pairs");
+        let b = text("b", SQLiteTextMode::String).in_table("pairs");
+        let x = integer("x", SQLiteIntegerMode::Number).in_table("targets");
+        let y = text("y", SQLiteTextMode::String).in_table("targets");
+
+        assert_eq!(
+            primary_key((&a, &b)).to_sql().unwrap(),
+            r#"PRIMARY KEY ("a", "b")"#
+        );
+        assert_eq!(
+            unique("pairs_b_a_unique").on((&b, &a)).to_sql().unwrap(),
+            r#"CONSTRAINT "pairs_b_a_unique" UNIQUE ("b", "a")"#
+        );
+        assert_eq!(
+            foreign_key((&a, &b))
+                .references(|| (x.clone(), y.clone()))
+                .on_delete(SQLiteReferenceAction::SetNull)
+                .deferrable()
+                .to_sql()
+                .unwrap(),
+            r#"FOREIGN KEY ("a", "b") REFERENCES "targets" ("x", "y") ON DELETE SET NULL DEFERRABLE INITIALLY DEFERRED"#
+        );
+    }
+
+    #[test]
+    fn other_tables() {
+        let a = integer("a", SQLiteIntegerMode::Number).in_table("pairs");
+        let b = text("b", SQLiteTextMode::String).in_table("pairs");
+        let x = integer("x", SQLiteIntegerMode::Number).in_table("targets");
+        let y = text("y", SQLiteTextMode::String).in_table("targets");
+        let other = |table, column| SQLiteDefinitionError::OtherTable { table, column };
+
+        assert_eq!(
+            primary_key((&a, &y)).to_sql(),
+            Err(other("pairs", ("targets", "y")))
+        );
+        assert_eq!(
+            unique("pairs_a_unique")
+                .on((&x,))
+                .into_extra("pairs")
+                .unwrap_err(),
+            other("pairs", ("targets", "x"))
+        );
+        assert_eq!(
+            foreign_key((&a, &b))
+                .references(|| (x.clone(), b.clone()))
+                .to_sql(),
+            Err(other("targets", ("pairs", "b")))
+        );
+        assert_eq!(
+            foreign_key((&x, &y))
+                .references(|| (a.clone(), b.clone()))
+                .into_extra("pairs")
+                .unwrap_err()
+                .to_string(),
+            r#"column "targets"."x" does not belong to table "pairs""#
+        );
+        assert_eq!(
+            check("pairs_x", gt(&x, 0)).into_extra("pairs").unwrap_err(),
+            other("pairs", ("targets", "x"))
+        );
+    }
+
+    #[test]
+    fn checks() {
+        let a = integer("a", SQLiteIntegerMode::Number).in_table("pairs");
+        let ratio = real("ratio").in_table("pairs");
+
+        assert_eq!(
+            check("pairs_positive", or([Some(gt(&a, 0)), None]))
+                .to_sql()
+                .unwrap(),
+            r#"CONSTRAINT "pairs_positive" CHECK ("a" > 0)"#
+        );
+        assert_eq!(
+            check("pairs_any", and(Vec::<SQL<SQLiteValue, bool>>::new()))
+                .to_sql()
+                .unwrap(),
+            r#"CONSTRAINT "pairs_any" CHECK (1 = 1)"#
+        );
+        assert_eq!(
+            check("pairs_none", or(Vec::<SQL<SQLiteValue, bool>>::new()))
+                .to_sql()
+                .unwrap(),
+            r#"CONSTRAINT "pairs_none" CHECK (1 = 0)"#
+        );
+        assert!(matches!(
+            check("pairs_ratio", gt(&ratio, f64::NAN)).to_sql(),
+            Err(SQLiteDefinitionError::Inline(InlineError::Literal(SQLiteValue::Real(value))))
+                if value.is_nan()
+        ));
+    }
+
+    #[test]
+    fn table_constraints() {
+        crate::sqlite_table!("groups", {
+            org: text("org", SQLiteTextMode::String).not_null(),
+            slug: text("slug", SQLiteTextMode::String).not_null(),
+        }, |table| [
+            primary_key((&table.org, &table.slug)),
+        ]);
+
+        crate::sqlite_table!("grants", {
+            org: text("org", SQLiteTextMode::String).not_null(),
+            slug: text("slug", SQLiteTextMode::String).not_null(),
+            grantee: text("grantee", SQLiteTextMode::String).not_null(),
+        }, |table| [
+            unique("grants_grantee_unique").on((&table.grantee, &table.org, &table.slug)),
+            foreign_key((&table.org, &table.slug))
+                .references(|| {
+                    let groups = Groups::new();
+                    (groups.org, groups.slug)
+                })
+                .on_delete(SQLiteReferenceAction::Cascade),
+        ], [strict]);
+
+        let groups = Groups::new().to_sql().unwrap();
+        let grants = Grants::new().to_sql().unwrap();
+
+        assert_eq!(
+            groups,
+            r#"CREATE TABLE "groups" ("org" TEXT NOT NULL, "slug" TEXT NOT NULL, PRIMARY KEY ("org", "slug"))"#
+        );
+        assert_eq!(
+            grants,
+            concat!(
+                r#"CREATE TABLE "grants" ("org" TEXT NOT NULL, "slug" TEXT NOT NULL, "grantee" TEXT NOT NULL, "#,
+                r#"CONSTRAINT "grants_grantee_unique" UNIQUE ("grantee", "org", "slug"), "#,
+                r#"FOREIGN KEY ("org", "slug") REFERENCES "groups" ("org", "slug") ON DELETE CASCADE) STRICT"#
+            )
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute("PRAGMA foreign_keys = ON", ()).unwrap();
+        conn.execute(&groups, ()).unwrap();
+        conn.execute(&grants, ()).unwrap();
+        conn.execute(r#"INSERT INTO "groups" VALUES ('acme', 'ops')"#, ())
+            .unwrap();
+        assert!(conn
+            .execute(r#"INSERT INTO "groups" VALUES ('acme', 'ops')"#, ())
+            .is_err());
+        conn.execute(r#"INSERT INTO "grants" VALUES ('acme', 'ops', 'bob')"#, ())
+            .unwrap();
+        assert!(conn
+            .execute(r#"INSERT INTO "grants" VALUES ('acme', 'ops', 'bob')"#, ())
+            .is_err());
+        assert!(conn
+            .execute(r#"INSERT INTO "grants" VALUES ('acme', 'dev', 'bob')"#, ())
+            .is_err());
+    }
+
+    #[test]
+    fn check_constraints() {
+        use common::expressions::conditions::{and, gt, gte, is_not_null, like, or};
+
+        crate::sqlite_table!("bookings", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            email: text("email", SQLiteTextMode::String).check("bookings_email_format", |email| like(email, "%@%")),
+            starts_at: integer("starts_at", SQLiteIntegerMode::Number).not_null(),
+            ends_at: integer("ends_at", SQLiteIntegerMode::Number),
+        }, |table| [
+            check("bookings_range", or([
+                and([is_not_null(&table.ends_at), gte(&table.ends_at, &table.starts_at)]),
+                gt(&table.starts_at, 0),
+            ])),
+        ]);
+
+        let bookings = Bookings::new().to_sql().unwrap();
+
+        assert_eq!(
+            bookings,
+            concat!(
+                r#"CREATE TABLE "bookings" ("id" INTEGER PRIMARY KEY, "#,
+                r#""email" TEXT CONSTRAINT "bookings_email_format" CHECK ("email" LIKE '%@%'), "#,
+                r#""starts_at" INTEGER NOT NULL, "ends_at" INTEGER, "#,
+                r#"CONSTRAINT "bookings_range" CHECK (((("ends_at" IS NOT NULL) AND ("ends_at" >= "starts_at")) OR ("starts_at" > 0))))"#
+            )
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&bookings, ()).unwrap();
+        conn.execute(
+            r#"INSERT INTO "bookings" VALUES (1, 'a@example.com', 5, 10)"#,
+            (),
+        )
+        .unwrap();
+        assert!(conn
+            .execute(r#"INSERT INTO "bookings" VALUES (2, 'nobody', 5, 10)"#, ())
+            .is_err());
+        assert!(conn
+            .execute(
+                r#"INSERT INTO "bookings" VALUES (3, 'c@example.com', 0, NULL)"#,
+                ()
+            )
+            .is_err());
+
+        crate::sqlite_table!("vouchers", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            booking_id: integer("booking_id", SQLiteIntegerMode::Number).check("vouchers_booking", |_| gt(&Bookings::new().id, 0)),
+        });
+
+        assert_eq!(
+            Vouchers::new().to_sql(),
+            Err(SQLiteDefinitionError::OtherTable {
+                table: "vouchers",
+                column: ("bookings", "id"),
+            })
+        );
+    }
+}
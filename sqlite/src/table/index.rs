@@ -0,0 +1,323 @@
+use common::{expressions::Expression, SQLFragment, SQL};
+
+use crate::{
+    common::{Collation, SQLiteDefinitionError, SQLiteValue},
+    table::check_table,
+    traits::table::{IntoSQLiteTableExtra, SQLiteTableExtra},
+};
+
+/// A term of an index: a column or expression, optionally with a collation or sort order.
+pub trait SQLiteIndexTerm {
+    fn term(self) -> SQL<SQLiteValue>;
+}
+
+impl<E: Expression<SQLiteValue>> SQLiteIndexTerm for E {
+    fn term(self) -> SQL<SQLiteValue> {
+        self.into_sql().cast()
+    }
+}
+
+/// A tuple of index terms, in index order.
+pub trait SQLiteIndexTerms {
+    fn terms(self) -> Vec<SQL<SQLiteValue>>;
+}
+
+macro_rules! impl_sqlite_index_terms {
+    ($head:ident $(, $tail:ident)*) => {
+        impl<$head: SQLiteIndexTerm, $($tail: SQLiteIndexTerm),*> SQLiteIndexTerms for ($head, $($tail,)*) {
+            #[allow(non_snake_case)]
+            fn terms(self) -> Vec<SQL<SQLiteValue>> {
+                let ($head, $($tail,)*) = self;
+                vec![$head.term(), $($tail.term()),*]
+            }
+        }
+    };
+}
+
+impl_sqlite_index_terms!(A);
+impl_sqlite_index_terms!(A, B);
+impl_sqlite_index_terms!(A, B, C);
+impl_sqlite_index_terms!(A, B, C, D);
+impl_sqlite_index_terms!(A, B, C, D, E);
+impl_sqlite_index_terms!(A, B, C, D, E, F);
+impl_sqlite_index_terms!(A, B, C, D, E, F, G);
+impl_sqlite_index_terms!(A, B, C, D, E, F, G, H);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SQLiteSortOrder {
+    Asc,
+    Desc,
+}
+
+/// An index term with an explicit collation and/or sort order.
+#[derive(Debug, Clone)]
+pub struct SQLiteIndexedColumn {
+    term: SQL<SQLiteValue>,
+    collation: Option<Collation>,
+    order: Option<SQLiteSortOrder>,
+}
+
+impl SQLiteIndexedColumn {
+    pub fn collate(self, collation: impl Into<Collation>) -> Self {
+        Self {
+            collation: Some(collation.into()),
+            ..self
+        }
+    }
+
+    pub fn asc(self) -> Self {
+        Self {
+            order: Some(SQLiteSortOrder::Asc),
+            ..self
+        }
+    }
+
+    pub fn desc(self) -> Self {
+        Self {
+            order: Some(SQLiteSortOrder::Desc),
+            ..self
+        }
+    }
+}
+
+impl SQLiteIndexTerm for SQLiteIndexedColumn {
+    fn term(self) -> SQL<SQLiteValue> {
+        let mut term = self.term;
+
+        if let Some(collation) = self.collation {
+            term = term.push_str(" COLLATE ").append(collation.into_fragment());
+        }
+
+        match self.order {
+            Some(SQLiteSortOrder::Asc) => term.push_str(" ASC"),
+            Some(SQLiteSortOrder::Desc) => term.push_str(" DESC"),
+            None => term,
+        }
+    }
+}
+
+/// Wraps a column or expression so a collation or sort order can be attached to it, e.g.
+/// `indexed(&table.email).collate(Collation::NoCase).desc()`.
+pub fn indexed<T: SQLiteIndexTerm>(term: T) -> SQLiteIndexedColumn {
+    SQLiteIndexedColumn {
+        term: term.term(),
+        collation: None,
+        order: None,
+    }
+}
+
+/// Index waiting for the terms it covers.
+#[derive(Debug, Clone, Copy)]
+pub struct SQLiteIndexBuilder {
+    name: &'static str,
+    unique: bool,
+}
+
+pub fn index(name: &'static str) -> SQLiteIndexBuilder {
+    SQLiteIndexBuilder {
+        name,
+        unique: false,
+    }
+}
+
+pub fn unique_index(name: &'static str) -> SQLiteIndexBuilder {
+    SQLiteIndexBuilder { name, unique: true }
+}
+
+impl SQLiteIndexBuilder {
+    pub fn on<T: SQLiteIndexTerms>(self, terms: T) -> SQLiteIndex {
+        SQLiteIndex {
+            name: self.name,
+            unique: self.unique,
+            terms: terms.terms(),
+            where_clause: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SQLiteIndex {
+    name: &'static str,
+    unique: bool,
+    terms: Vec<SQL<SQLiteValue>>,
+    where_clause: Option<SQL<SQLiteValue, bool>>,
+}
+
+impl SQLiteIndex {
+    /// Makes this a partial index covering only the rows matching `condition`.
+    pub fn where_(self, condition: SQL<SQLiteValue, bool>) -> Self {
+        Self {
+            where_clause: Some(condition),
+            ..self
+        }
+    }
+
+    /// The `CREATE INDEX` statement of this index on `table`, failing when a term or the
+    /// `WHERE` clause refers to a column of another table.
+    pub fn create(self, table: &'static str) -> Result<SQL<SQLiteValue>, SQLiteDefinitionError> {
+        let columns: Vec<_> = self
+            .terms
+            .iter()
+            .flat_map(SQL::columns)
+            .chain(self.where_clause.iter().flat_map(SQL::columns))
+            .collect();
+        check_table(table, &columns)?;
+
+        let sql = SQL::raw(if self.unique {
+            "CREATE UNIQUE INDEX IF NOT EXISTS "
+        } else {
+            "CREATE INDEX IF NOT EXISTS "
+        })
+        .append(SQL::name(self.name))
+        .push_str(" ON ")
+        .append(SQL::table(table))
+        .push_str(" ")
+        .nest(SQL::<SQLiteValue>::join(self.terms, ", "));
+
+        Ok(match self.where_clause {
+            Some(condition) => sql.push_str(" WHERE ").append(condition),
+            None => sql,
+        })
+    }
+}
+
+impl IntoSQLiteTableExtra for SQLiteIndex {
+    fn into_extra(self, table: &'static str) -> Result<SQLiteTableExtra, SQLiteDefinitionError> {
+        Ok(SQLiteTableExtra::Index(self.create(table)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{
+        expressions::conditions::{gt, is_not_null},
+        sql,
+        traits::ColumnBuilder,
+    };
+
+    use crate::{
+        columns::{
+            integer::{integer, SQLiteIntegerMode},
+            text::{text, SQLiteTextMode},
+        },
+        common::{Collation, SQLiteDefinitionError, SQLiteDialect},
+        expressions::{add, collate},
+        prelude::*,
+    };
+
+    use super::{index, indexed, unique_index};
+
+    #[test]
+    fn create() {
+        let email = text("email", SQLiteTextMode::String).in_table("users");
+        let age = integer("age", SQLiteIntegerMode::Number).in_table("users");
+
+        assert_eq!(
+            index("users_age_idx")
+                .on((&age,))
+                .create("users")
+                .unwrap()
+                .build_inline::<SQLiteDialect>()
+                .unwrap(),
+            r#"CREATE INDEX IF NOT EXISTS "users_age_idx" ON "users" ("age")"#
+        );
+        assert_eq!(
+            unique_index("users_email_idx")
+                .on((
+                    indexed(&email).collate(Collation::NoCase),
+                    indexed(&age).desc()
+                ))
+                .where_(is_not_null(&age))
+                .create("users")
+                .unwrap()
+                .build_inline::<SQLiteDialect>()
+                .unwrap(),
+            concat!(
+                r#"CREATE UNIQUE INDEX IF NOT EXISTS "users_email_idx" ON "users" "#,
+                r#"("email" COLLATE NOCASE, "age" DESC) WHERE "age" IS NOT NULL"#
+            )
+        );
+        assert_eq!(
+            index("users_lower_email_idx")
+                .on((indexed(sql!("lower({})", &email)).asc(), add(&age, 1)))
+                .where_(gt(collate(&email, Collation::RTrim), ""))
+                .create("users")
+                .unwrap()
+                .build_inline::<SQLiteDialect>()
+                .unwrap(),
+            concat!(
+                r#"CREATE INDEX IF NOT EXISTS "users_lower_email_idx" ON "users" "#,
+                r#"((lower("email")) ASC, ("age" + 1)) WHERE ("email" COLLATE RTRIM) > ''"#
+            )
+        );
+    }
+
+    #[test]
+    fn other_tables() {
+        let email = text("email", SQLiteTextMode::String).in_table("users");
+        let total = integer("total", SQLiteIntegerMode::Number).in_table("orders");
+        let other = SQLiteDefinitionError::OtherTable {
+            table: "users",
+            column: ("orders", "total"),
+        };
+
+        assert_eq!(
+            index("users_total_idx")
+                .on((add(&total, 1),))
+                .create("users")
+                .unwrap_err(),
+            other
+        );
+        assert_eq!(
+            index("users_email_idx")
+                .on((&email,))
+                .where_(gt(&total, 0))
+                .create("users")
+                .unwrap_err(),
+            other
+        );
+    }
+
+    #[test]
+    fn table_indexes() {
+        use common::expressions::conditions::is_not_null;
+
+        crate::sqlite_table!("people", {
+            id: integer("id", SQLiteIntegerMode::Number).primary(),
+            email: text("email", SQLiteTextMode::String).not_null(),
+            age: integer("age", SQLiteIntegerMode::Number),
+        }, |table| [
+            unique_index("people_email_idx").on((indexed(&table.email).collate(Collation::NoCase),)),
+            index("people_age_idx")
+                .on((indexed(&table.age).desc(), &table.id))
+                .where_(is_not_null(&table.age)),
+        ]);
+
+        let people = People::new().to_sql().unwrap();
+
+        assert_eq!(
+            people,
+            concat!(
+                r#"CREATE TABLE "people" ("id" INTEGER PRIMARY KEY, "email" TEXT NOT NULL, "age" INTEGER);"#,
+                "\n",
+                r#"CREATE UNIQUE INDEX IF NOT EXISTS "people_email_idx" ON "people" ("email" COLLATE NOCASE);"#,
+                "\n",
+                r#"CREATE INDEX IF NOT EXISTS "people_age_idx" ON "people" ("age" DESC, "id") WHERE "age" IS NOT NULL"#
+            )
+        );
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(&people).unwrap();
+        conn.execute(
+            r#"INSERT INTO "people" ("email") VALUES ('Ann@example.com')"#,
+            (),
+        )
+        .unwrap();
+        assert!(conn
+            .execute(
+                r#"INSERT INTO "people" ("email") VALUES ('ann@EXAMPLE.com')"#,
+                ()
+            )
+            .is_err());
+    }
+}
@@ -1,26 +1,228 @@
 use std::marker::PhantomData;
 
-use crate::traits::table::Strict;
+use common::{traits::Unique, SQL};
 
-#[derive(Debug, Default)]
-struct NotStrict {}
+use crate::{
+    columns::integer::NotAutoIncremented,
+    common::{SQLiteDefinitionError, SQLiteValue},
+    traits::{
+        column::{Autoincrement, SQLiteAutoincrementKey, SQLiteUniqueKey},
+        table::{RowID, SQLStrict, SQLWithoutRowID, SQLiteAutoincrementRowID, Strict},
+    },
+};
+
+pub mod constraints;
+pub mod index;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NotStrict;
 
 impl Strict for NotStrict {
     const IS_STRICT: bool = false;
 }
 
-#[derive(Debug, Default)]
-struct IsStrict {}
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IsStrict;
 
 impl Strict for IsStrict {
     const IS_STRICT: bool = true;
 }
 
-#[derive(Debug, Default)]
-struct WithoutRowID {}
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WithRowID;
+
+impl RowID for WithRowID {
+    const WITHOUT_ROWID: bool = false;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WithoutRowID;
+
+impl RowID for WithoutRowID {
+    const WITHOUT_ROWID: bool = true;
+}
+
+impl<A: Autoincrement> SQLiteAutoincrementRowID<A> for WithRowID {}
+
+impl SQLiteAutoincrementRowID<NotAutoIncremented> for WithoutRowID {}
+
+/// Fails on the first of `columns`, given as table and name, that does not belong to `table`.
+pub(crate) fn check_table(
+    table: &'static str,
+    columns: &[(&'static str, &'static str)],
+) -> Result<(), SQLiteDefinitionError> {
+    match columns.iter().find(|(other, _)| *other != table) {
+        Some(&(other, column)) => Err(SQLiteDefinitionError::OtherTable {
+            table,
+            column: (other, column),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Rejects at compile time a column whose key states are invalid in a table with rowid state
+/// `R`; called by `sqlite_table!` for every column.
+#[doc(hidden)]
+pub const fn check_column<P, U, A, R>()
+where
+    P: SQLiteUniqueKey<U> + SQLiteAutoincrementKey<A>,
+    U: Unique,
+    A: Autoincrement,
+    R: SQLiteAutoincrementRowID<A>,
+{
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SQLiteTableBuilder<S: Strict = NotStrict, R: RowID = WithRowID> {
+    pub(crate) name: &'static str,
+    pub(crate) if_not_exists: bool,
+    _marker: PhantomData<(S, R)>,
+}
+
+impl SQLiteTableBuilder {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+}
+
+impl<S: Strict, R: RowID> SQLiteTableBuilder<S, R> {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Renders `CREATE TABLE IF NOT EXISTS` instead of a plain `CREATE TABLE`.
+    pub fn if_not_exists(self) -> Self {
+        Self {
+            if_not_exists: true,
+            ..self
+        }
+    }
+
+    /// The `CREATE TABLE` statement wrapping the given column and constraint definitions.
+    pub fn create(&self, definitions: Vec<SQL<SQLiteValue>>) -> SQL<SQLiteValue> {
+        let sql = SQL::raw(if self.if_not_exists {
+            "CREATE TABLE IF NOT EXISTS "
+        } else {
+            "CREATE TABLE "
+        })
+        .append(SQL::table(self.name))
+        .push_str(" ")
+        .nest(SQL::<SQLiteValue>::join(definitions, ", "));
+
+        let mut options = vec![];
+
+        if S::IS_STRICT {
+            options.push("STRICT");
+        }
+
+        if R::WITHOUT_ROWID {
+            options.push("WITHOUT ROWID");
+        }
+
+        if options.is_empty() {
+            sql
+        } else {
+            sql.push_str(format!(" {}", options.join(", ")))
+        }
+    }
+}
+
+impl<R: RowID> SQLStrict for SQLiteTableBuilder<NotStrict, R> {
+    type Value = SQLiteTableBuilder<IsStrict, R>;
+
+    fn strict(self) -> Self::Value {
+        SQLiteTableBuilder {
+            name: self.name,
+            if_not_exists: self.if_not_exists,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: Strict> SQLWithoutRowID for SQLiteTableBuilder<S, WithRowID> {
+    type Value = SQLiteTableBuilder<S, WithoutRowID>;
+
+    fn without_rowid(self) -> Self::Value {
+        SQLiteTableBuilder {
+            name: self.name,
+            if_not_exists: self.if_not_exists,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::SQL;
+
+    use crate::{
+        common::SQLiteDialect,
+        prelude::*,
+        traits::table::{SQLStrict, SQLWithoutRowID},
+    };
+
+    use super::SQLiteTableBuilder;
+
+    #[test]
+    fn create() {
+        let columns = || vec![SQL::name("id").push_str(" INTEGER PRIMARY KEY")];
+
+        assert_eq!(
+            SQLiteTableBuilder::new("users")
+                .create(columns())
+                .build_inline::<SQLiteDialect>()
+                .unwrap(),
+            r#"CREATE TABLE "users" ("id" INTEGER PRIMARY KEY)"#
+        );
+        assert_eq!(
+            SQLiteTableBuilder::new("users")
+                .if_not_exists()
+                .strict()
+                .without_rowid()
+                .create(columns())
+                .build_inline::<SQLiteDialect>()
+                .unwrap(),
+            r#"CREATE TABLE IF NOT EXISTS "users" ("id" INTEGER PRIMARY KEY) STRICT, WITHOUT ROWID"#
+        );
+    }
+
+    #[test]
+    fn create_table() {
+        crate::sqlite_table!("accounts", {
+            id: integer("id", SQLiteIntegerMode::Number).primary().autoincrement(),
+            email: text("email", SQLiteTextMode::String).not_null().unique("accounts_email_unique"),
+            balance: real("balance"),
+            tag: any("tag"),
+            amount: number("amount"),
+        });
+
+        crate::sqlite_table!("sessions", {
+            token: text("token", SQLiteTextMode::String).primary(),
+            payload: blob("payload", SQLiteBlobMode::Buffer).not_null(),
+        }, [strict, without_rowid, if_not_exists]);
+
+        let accounts = Accounts::new().to_sql().unwrap();
+        let sessions = Sessions::new().to_sql().unwrap();
+
+        assert_eq!(
+            accounts,
+            concat!(
+                r#"CREATE TABLE "accounts" ("id" INTEGER PRIMARY KEY AUTOINCREMENT, "#,
+                r#""email" TEXT NOT NULL CONSTRAINT "accounts_email_unique" UNIQUE, "#,
+                r#""balance" REAL, "tag" ANY, "amount" NUMERIC)"#
+            )
+        );
+        assert_eq!(
+            sessions,
+            r#"CREATE TABLE IF NOT EXISTS "sessions" ("token" TEXT PRIMARY KEY, "payload" BLOB NOT NULL) STRICT, WITHOUT ROWID"#
+        );
 
-#[derive(Debug, Default)]
-struct SQLiteTableBuilder<S = NotStrict> {
-    name: &'static str,
-    _strict: PhantomData<S>,
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(&accounts, ()).unwrap();
+        conn.execute(&sessions, ()).unwrap();
+        conn.execute(&sessions, ()).unwrap();
+    }
 }
@@ -1,11 +1,198 @@
-use crate::columns::SQLiteColumn;
+use common::{
+    traits::{ColumnBuilder, NotNull, PrimaryKey, Unique},
+    SQL,
+};
+
+use crate::{
+    columns::integer::IsAutoIncremented,
+    common::{SQLiteDecodeError, SQLiteEncodeError, SQLiteValue},
+    query::{insert::SQLiteInsertError, update::SQLiteUpdateError},
+};
+
+/// Exposes the Rust type a column holds and how inserts and updates fill it in.
+pub trait SQLiteColumnData {
+    type DataType;
+    /// Type accepted for the column by inserts; uninhabited for generated columns.
+    type Writable;
+    /// Type accepted for the column by updates: `Writable`, wrapped in an `Option` for nullable
+    /// columns so `None` can set them to `NULL`.
+    type Assignable;
+
+    /// Resolves the value inserted into this column, calling `default_fn` when `value` is
+    /// omitted. `None` leaves the column out of the statement so SQLite applies its own default.
+    fn insert_value(
+        &self,
+        value: Option<Self::Writable>,
+    ) -> Result<Option<SQL<SQLiteValue>>, SQLiteInsertError>;
+
+    /// Resolves the value assigned to this column on update, falling back to `on_update_fn` or
+    /// `on_update_sql` when `value` is omitted. `None` leaves the column untouched.
+    fn update_value(
+        &self,
+        value: Option<Self::Assignable>,
+    ) -> Result<Option<SQL<SQLiteValue>>, SQLiteUpdateError>;
+
+    /// Selects the column as the value its mode reads back, e.g. `json("settings")` for JSONB.
+    fn read_sql(&self) -> SQL<SQLiteValue, Self::DataType>;
+}
+
+/// Maps a column's storage type to the type name used in its column definition.
+pub trait SQLiteColumnType: Default + Clone {
+    const SQL_TYPE: &'static str;
+}
+
+/// Storage mode of a column, converting its Rust values to what SQLite stores and back.
+pub trait SQLiteDataMode<T: Clone>: Default + Clone {
+    /// Converts a value for storage, failing when the mode cannot represent it.
+    fn to_sql_value(value: T) -> Result<SQLiteValue, SQLiteEncodeError>;
+
+    /// Reads a stored value back, failing when it is not one this mode writes.
+    fn from_sql_value(value: SQLiteValue) -> Result<T, SQLiteDecodeError>;
+
+    /// Wraps a value from `to_sql_value` in the SQL that converts it to what the column stores.
+    fn write_sql(value: SQLiteValue) -> SQL<SQLiteValue> {
+        SQL::param(value)
+    }
+
+    /// Wraps a reference to the column in the SQL that converts it to what `from_sql_value`
+    /// accepts.
+    fn read_sql(column: SQL<SQLiteValue>) -> SQL<SQLiteValue> {
+        column
+    }
+}
+
+/// Renders a value as an inline SQLite literal, e.g. for `DEFAULT` clauses.
+pub trait SQLiteLiteral {
+    /// Returns `None` when the value has no SQLite literal form, such as NaN or infinite reals.
+    fn to_literal(&self) -> Option<String>;
+}
+
+pub trait Generated: Clone + Copy + Default {
+    const IS_GENERATED: bool;
+    /// Type accepted for the column by inserts and updates.
+    type Writable<T>;
+
+    /// Unwraps a value accepted by inserts and updates into the column's Rust type.
+    fn written<T>(value: Self::Writable<T>) -> T;
+}
+
+/// Not-null state of a column, picking the type updates assign to it.
+pub trait Nullability: NotNull {
+    /// `T` for `NOT NULL` columns, `Option<T>` for nullable ones.
+    type Assignable<T>;
+
+    /// Unwraps an assigned value, `None` standing for `NULL`.
+    fn assigned<T>(value: Self::Assignable<T>) -> Option<T>;
+}
 
 pub trait Autoincrement: Clone + Copy + Default {
     const AUTOINCREMENT: bool;
 }
 
+/// Implemented by the primary key states that accept the autoincrement state `A`.
+#[diagnostic::on_unimplemented(
+    message = "`AUTOINCREMENT` is only allowed on an `INTEGER PRIMARY KEY` column",
+    label = "call `.primary()` before `.autoincrement()`"
+)]
+pub trait SQLiteAutoincrementKey<A: Autoincrement>: PrimaryKey {}
+
+/// Implemented by the primary key states that accept the unique state `U`.
+#[diagnostic::on_unimplemented(
+    message = "a `PRIMARY KEY` column is already unique and cannot also be `UNIQUE`",
+    label = "remove either `.primary()` or `.unique(..)`"
+)]
+pub trait SQLiteUniqueKey<U: Unique>: PrimaryKey {}
+
 pub trait SQLAutoIncrement: Clone {
+    type Primary: PrimaryKey;
+    type Value;
+
+    fn autoincrement(self) -> Self::Value
+    where
+        Self::Primary: SQLiteAutoincrementKey<IsAutoIncremented>;
+}
+
+pub trait SQLPrimaryOnConflict: Clone {
+    type Conflict;
+
+    fn primary_on_conflict(self, conflict: Self::Conflict) -> Self;
+}
+
+pub trait SQLNotNullOnConflict: Clone {
+    type Conflict;
+
+    fn not_null_on_conflict(self, conflict: Self::Conflict) -> Self;
+}
+
+pub trait SQLUniqueOnConflict: Clone {
+    type Conflict;
+
+    fn unique_on_conflict(self, conflict: Self::Conflict) -> Self;
+}
+
+pub trait SQLReferences: Clone {
+    type DataType;
+    type Value;
+
+    /// Points this column at the column returned by `column`, which must hold the same data
+    /// type. The closure only runs when the definition is rendered, so tables can reference
+    /// themselves or each other.
+    fn references<C, F>(self, column: F) -> Self::Value
+    where
+        C: SQLiteColumnData<DataType = Self::DataType> + ColumnBuilder,
+        F: Fn() -> C + Send + Sync + 'static;
+}
+
+pub trait SQLReferenceActions: Clone {
+    type Action;
+
+    fn on_delete(self, action: Self::Action) -> Self;
+    fn on_update(self, action: Self::Action) -> Self;
+    /// Defers the foreign key check until the transaction commits.
+    fn deferrable(self) -> Self;
+}
+
+/// A column, or a reference to one, named by a table-level constraint.
+pub trait SQLiteColumnRef {
+    type DataType;
+
+    fn table_name(&self) -> &'static str;
+    fn column_name(&self) -> &'static str;
+}
+
+pub trait SQLGenerated: Clone {
+    type Expression;
     type Value;
 
-    fn autoincrement(self) -> Self::Value;
+    /// Computes the column from `expression`, which receives the table so it can refer to
+    /// sibling columns; columns of other tables fail the definition. The column is `VIRTUAL`
+    /// unless `stored` is called.
+    fn generated_always_as<T, F>(self, expression: F) -> Self::Value
+    where
+        T: Default,
+        F: Fn(&T) -> Self::Expression + Send + Sync + 'static;
+}
+
+pub trait SQLGeneratedStored: Clone {
+    fn stored(self) -> Self;
+}
+
+pub trait SQLCollate: Clone {
+    type Collation;
+
+    fn collate(self, collation: impl Into<Self::Collation>) -> Self;
+}
+
+pub trait SQLCheck: Sized {
+    type Expression;
+
+    /// Adds a named `CHECK` constraint built from the column itself.
+    fn check<F>(self, name: &'static str, expression: F) -> Self
+    where
+        F: FnOnce(&Self) -> Self::Expression;
+}
+
+pub trait SQLCheckValues: SQLCheck {
+    /// Adds a named `CHECK` constraint allowing only the values the column's type can hold.
+    fn check_values(self, name: &'static str) -> Self;
 }
@@ -1,2 +1,2 @@
-pub(crate) mod column;
-pub(crate) mod table;
+pub mod column;
+pub mod table;
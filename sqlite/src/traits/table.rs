@@ -1,3 +1,91 @@
-pub trait Strict {
+use common::SQL;
+
+use crate::{
+    common::{SQLiteDefinitionError, SQLiteDialect, SQLiteValue},
+    query::{insert::SQLiteInsertError, update::SQLiteUpdateError},
+    traits::column::Autoincrement,
+};
+
+/// Implemented by the structs generated with `sqlite_table!`.
+pub trait SQLiteTable {
+    const NAME: &'static str;
+
+    /// Row accepted by inserts, with one optional field per column.
+    type Insert: Default;
+
+    /// Resolves the column names and values of an insert of `values`.
+    fn insert_values(
+        &self,
+        values: Self::Insert,
+    ) -> Result<Vec<(&'static str, SQL<SQLiteValue>)>, SQLiteInsertError>;
+
+    /// Row accepted by updates, with one optional field per column; `None` leaves the column out
+    /// and nullable columns take `Some(None)` to be set to `NULL`.
+    type Update: Default;
+
+    /// Resolves the column assignments of an update setting `values`.
+    fn update_values(
+        &self,
+        values: Self::Update,
+    ) -> Result<Vec<(&'static str, SQL<SQLiteValue>)>, SQLiteUpdateError>;
+}
+
+pub trait Strict: Default {
     const IS_STRICT: bool;
 }
+
+pub trait RowID: Default {
+    const WITHOUT_ROWID: bool;
+}
+
+/// Implemented by the rowid states of a table that accept columns with the autoincrement
+/// state `A`.
+#[diagnostic::on_unimplemented(
+    message = "`AUTOINCREMENT` is not allowed on a `WITHOUT ROWID` table",
+    label = "remove `.autoincrement()` or the `without_rowid` option"
+)]
+pub trait SQLiteAutoincrementRowID<A: Autoincrement>: RowID {}
+
+pub trait SQLStrict {
+    type Value;
+
+    fn strict(self) -> Self::Value;
+}
+
+pub trait SQLWithoutRowID {
+    type Value;
+
+    fn without_rowid(self) -> Self::Value;
+}
+
+/// A tuple of columns of one table, such as the key of a composite constraint.
+pub trait SQLiteColumns {
+    /// Tuple of the columns' data types, used to match foreign keys against their target.
+    type DataTypes;
+
+    /// Table and name of each column, in order.
+    fn columns(&self) -> Vec<(&'static str, &'static str)>;
+}
+
+/// A column, table constraint or table, defined by DDL.
+pub trait SQLiteDefinition: Sized {
+    fn definition(self) -> Result<SQL<SQLiteValue>, SQLiteDefinitionError>;
+
+    /// Renders the definition with its values inlined, as SQLite cannot bind parameters in DDL.
+    fn to_sql(self) -> Result<String, SQLiteDefinitionError> {
+        Ok(self.definition()?.build_inline::<SQLiteDialect>()?)
+    }
+}
+
+/// Entry of the `|table| [...]` list of `sqlite_table!`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SQLiteTableExtra {
+    /// Definition placed inside `CREATE TABLE`, after the columns.
+    Constraint(SQL<SQLiteValue>),
+    /// Statement executed after `CREATE TABLE`.
+    Index(SQL<SQLiteValue>),
+}
+
+pub trait IntoSQLiteTableExtra {
+    fn into_extra(self, table: &'static str) -> Result<SQLiteTableExtra, SQLiteDefinitionError>;
+}
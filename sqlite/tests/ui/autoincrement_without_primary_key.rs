@@ -0,0 +1,7 @@
+use sqlite::sqlite_table;
+
+sqlite_table!("users", {
+    id: integer("id", SQLiteIntegerMode::Number).autoincrement(),
+});
+
+fn main() {}
@@ -0,0 +1,7 @@
+use sqlite::sqlite_table;
+
+sqlite_table!("users", {
+    id: integer("id", SQLiteIntegerMode::Number).primary().autoincrement(),
+}, [strict, without_rowid]);
+
+fn main() {}
@@ -0,0 +1,5 @@
+use sqlite::prelude::*;
+
+fn main() {
+    let _ = integer("id", SQLiteIntegerMode::Number).not_null().autoincrement();
+}
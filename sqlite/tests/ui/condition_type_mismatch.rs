@@ -0,0 +1,13 @@
+use common::{expressions::conditions::eq, SQL};
+use sqlite::{common::SQLiteValue, sqlite_table};
+
+sqlite_table!("users", {
+    id: integer("id", SQLiteIntegerMode::Number).primary(),
+});
+
+fn main() {
+    let users = Users::new();
+
+    let _: SQL<SQLiteValue, bool> = eq(users.id, 42);
+    let _: SQL<SQLiteValue, bool> = eq(users.id, "x");
+}
@@ -0,0 +1,6 @@
+use common::SQL;
+use sqlite::{common::SQLiteValue, prelude::sql};
+
+fn main() {
+    let _: SQL<SQLiteValue> = sql!("SELECT {} FROM {}", 1);
+}
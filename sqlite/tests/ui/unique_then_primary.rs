@@ -0,0 +1,7 @@
+use sqlite::sqlite_table;
+
+sqlite_table!("users", {
+    id: integer("id", SQLiteIntegerMode::Number).unique("users_id_unique").primary().autoincrement(),
+});
+
+fn main() {}
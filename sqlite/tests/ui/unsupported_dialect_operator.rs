@@ -0,0 +1,25 @@
+use common::{
+    expressions::conditions::{array_contains, ilike},
+    SQL,
+};
+use sqlite::{common::SQLiteValue, sqlite_table};
+
+sqlite_table!("users", {
+    id: integer("id", SQLiteIntegerMode::Number).primary(),
+    name: text("name", SQLiteTextMode::String).not_null(),
+});
+
+struct Value;
+
+impl From<String> for Value {
+    fn from(_: String) -> Self {
+        Value
+    }
+}
+
+fn main() {
+    let users = Users::new();
+
+    let _: SQL<Value, bool> = ilike("Ann", "a%");
+    let _: SQL<SQLiteValue, bool> = array_contains(users.name, ["a"]);
+}